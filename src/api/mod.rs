@@ -2,9 +2,11 @@
 //! Currently only `/api/v1`.
 use hyper;
 
+pub mod multipart;
 pub mod v1;
 pub mod oauth;
 
+pub use self::v1::async_client::AsyncClient;
 pub use self::v1::client::Client;
 
 /// constructor of HTTPS requests, used in API Mehtods.
@@ -0,0 +1,107 @@
+//! This module contains a `multipart/form-data` encoder, used to upload files to the API.
+//!
+use errors::*;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static BOUNDARY_COUNT: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// A `multipart/form-data` body, built part by part.
+///
+/// let mut form = Multipart::new();
+/// form.text("description", "A cat")
+///     .file("file", "cat.png", "image/png", &data)?;
+/// let content_type = form.content_type();
+/// let body = form.finish();
+///
+#[derive(Debug)]
+pub struct Multipart {
+    boundary: String,
+    body: Vec<u8>
+}
+
+impl Multipart {
+    pub fn new() -> Multipart {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as u64 ^ d.as_secs())
+            .unwrap_or(0);
+        let count = BOUNDARY_COUNT.fetch_add(1, Ordering::SeqCst);
+        Multipart {
+            boundary: format!("herder-boundary-{:x}-{:x}", nanos, count),
+            body: Vec::new()
+        }
+    }
+
+    /// Returns the boundary that separates the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the value of the `Content-Type` header for this body.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Adds a text field.
+    pub fn text(&mut self, name: &str, value: &str) -> &mut Multipart {
+        self.start_part();
+        self.body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", escape(name)).as_bytes());
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// Adds a file field, with its file name and MIME type.
+    ///
+    /// The MIME type must be a plain `type/subtype`, since it is written as the `Content-Type`
+    /// header of the part.
+    pub fn file(&mut self, name: &str, filename: &str, mime_type: &str, data: &[u8]) -> Result<&mut Multipart> {
+        if !is_mime_type(mime_type) {
+            bail!("Invalid MIME type: {:?}", mime_type);
+        }
+        self.start_part();
+        self.body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                    escape(name), escape(filename)).as_bytes());
+        self.body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", mime_type).as_bytes());
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+        Ok(self)
+    }
+
+    /// Closes the body, returning its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+
+    fn start_part(&mut self) {
+        self.body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+    }
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Multipart::new()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\r', "").replace('\n', "")
+}
+
+/// Checks that `value` is a `type/subtype` MIME type, made of HTTP token characters only.
+fn is_mime_type(value: &str) -> bool {
+    let is_token = |part: &str| {
+        !part.is_empty() && part.chars().all(|c| match c {
+            'a'...'z' | 'A'...'Z' | '0'...'9' => true,
+            _ => "!#$%&'*+-.^_`|~".contains(c)
+        })
+    };
+    let mut parts = value.splitn(2, '/');
+    match (parts.next(), parts.next()) {
+        (Some(kind), Some(subtype)) => is_token(kind) && is_token(subtype),
+        _ => false
+    }
+}
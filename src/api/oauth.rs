@@ -1,12 +1,15 @@
-//! This module contains the code for registering new OAuth Applications, such as our `Client`s.
+//! This module contains the code for registering new OAuth Applications, such as our `Client`s,
+//! and for acquiring their access tokens.
 use curl::easy::{Easy, Form};
 use errors::*;
-use hyper::{Client, Body, Post, Uri};
+use hyper::{Body, Post, Uri};
 use hyper::client::Request;
 use hyper::header::ContentType;
-use hyper_tls::HttpsConnector;
-use futures::{Future, Stream};
-use tokio_core::reactor::Core;
+use futures::{future, Future, Stream};
+use reactor::{HttpsClient, Reactor};
+use serde_json;
+use serde_urlencoded;
+use url::Url;
 use url::form_urlencoded;
 
 use std::fmt;
@@ -38,6 +41,108 @@ impl OAuthApp {
         form.part("scopes").contents(b"read write follow").add().unwrap();
         form
     }
+
+    /// Returns the `/oauth/authorize` URL on `base_url`, where a user can authorize the app
+    /// for the given `scopes`, and obtain the code for `AuthorizationType::RequestAuthorization`.
+    pub fn authorize_url(&self, base_url: &Url, scopes: &str) -> Result<Url> {
+        let mut url = base_url.join("/oauth/authorize").chain_err(|| "invalid authorization URL")?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("response_type", "code")
+            .append_pair("scope", scopes);
+        Ok(url)
+    }
+
+    /// Requests an access token at `token_url` for the given grant, with the `http` client.
+    pub fn request_token(&self,
+                         token_url: &str,
+                         grant: AuthorizationType,
+                         scopes: &str,
+                         http: &HttpsClient) -> Box<Future<Item = AccessToken, Error = Error>> {
+        let body = match grant.form_encode(self, scopes) {
+            Ok(body) => body,
+            Err(e) => return Box::new(future::err(e))
+        };
+        Box::new(post_form(token_url, body, http).and_then(|body| {
+            serde_json::from_slice(&body).chain_err(|| "Unexpected JSON error fetching access token.")
+        }))
+    }
+
+    /// Revokes `token` at `revoke_url`, with the `http` client.
+    pub fn revoke_token(&self, revoke_url: &str, token: &str, http: &HttpsClient) -> Box<Future<Item = (), Error = Error>> {
+        let body = match self.revoke_form_encode(token) {
+            Ok(body) => body,
+            Err(e) => return Box::new(future::err(e))
+        };
+        Box::new(post_form(revoke_url, body, http).map(|_| ()))
+    }
+
+    /// Encodes the `/oauth/revoke` form data for `token`.
+    pub fn revoke_form_encode(&self, token: &str) -> Result<String> {
+        let params = [
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("token", token)
+        ];
+        serde_urlencoded::to_string(&params[..]).chain_err(|| "Could not encode revoke form data")
+    }
+}
+
+/// The OAuth grants to request an access token with.
+#[derive(Debug, PartialEq)]
+pub enum AuthorizationType {
+    /// Authorizes the app itself, not on behalf of any user.
+    ClientCredentials,
+    /// Authorizes the app with a user's e-mail and password.
+    Password {
+        user: String,
+        pass: String
+    },
+    /// Authorizes the app with the code obtained at `OAuthApp::authorize_url`.
+    RequestAuthorization { code: String }
+}
+
+impl AuthorizationType {
+    /// Encodes the `/oauth/token` form data for this grant.
+    pub fn form_encode(&self, app: &OAuthApp, scopes: &str) -> Result<String> {
+        let mut params = vec![
+            ("client_id", app.client_id.as_str()),
+            ("client_secret", app.client_secret.as_str())
+        ];
+        match *self {
+            AuthorizationType::ClientCredentials => {
+                params.push(("grant_type", "client_credentials"));
+            },
+            AuthorizationType::Password { ref user, ref pass } => {
+                params.push(("grant_type", "password"));
+                params.push(("username", user.as_str()));
+                params.push(("password", pass.as_str()));
+            },
+            AuthorizationType::RequestAuthorization { ref code } => {
+                params.push(("grant_type", "authorization_code"));
+                params.push(("redirect_uri", app.redirect_uri.as_str()));
+                params.push(("code", code.as_str()));
+            }
+        }
+        params.push(("scope", scopes));
+        serde_urlencoded::to_string(params).chain_err(|| "Could not encode token form data")
+    }
+}
+
+/// An OAuth access token, to be used as the Bearer token of a `Client`.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct AccessToken {
+    pub access_token: String,
+    pub token_type: String,
+    pub scope: String,
+    pub created_at: i64
+}
+
+impl AsRef<str> for AccessToken {
+    fn as_ref(&self) -> &str {
+        &self.access_token
+    }
 }
 
 impl fmt::Display for OAuthApp {
@@ -59,18 +164,22 @@ pub fn make_client(app: &OAuthApp, node_endpoint: &str) -> Easy {
 /// let social_app = CreateApp {
 ///     client_name: String::from("herder"),
 ///     redirect_uris: String::from("urn:ietf:wg:oauth:2.0:oob"),
-///     scopes: String::from("read write follow")
+///     scopes: String::from("read write follow"),
+///     website: None
 /// };
 ///
 /// let social_app = CreateApp::new("herder-app", "urn:ietf:wg:oauth:2.0:oob", "read write follow");
 ///
 /// let social_app = CreateApp::default();
 ///
+/// let social_app = CreateApp::default().website("https://example.com");
+///
 #[derive(Debug, Deserialize)]
 pub struct CreateApp {
     client_name: String,
     redirect_uris: String,
-    scopes: String
+    scopes: String,
+    website: Option<String>
 }
 
 impl Default for CreateApp {
@@ -78,7 +187,8 @@ impl Default for CreateApp {
         CreateApp {
             client_name: String::from("herder"),
             redirect_uris: String::from("urn:ietf:wg:oauth:2.0:oob"),
-            scopes: String::from("read")
+            scopes: String::from("read"),
+            website: None
         }
     }
 }
@@ -88,39 +198,69 @@ impl CreateApp {
         CreateApp {
             client_name: String::from(name),
             redirect_uris: String::from(uris),
-            scopes: String::from(scopes)
+            scopes: String::from(scopes),
+            website: None
         }
     }
 
-    pub fn register(&self, api_url: &str, dst: Arc<Mutex<Vec<u8>>>) -> Result<()> {
-        let mut core = Core::new().chain_err(|| "Could not start client reactor")?;
-        let client = Client::configure()
-            .connector(HttpsConnector::new(4, &core.handle()))
-            .build(&core.handle());
-
-        let url = Uri::from_str(api_url).chain_err(|| "Invalid registration URL")?;
-        let mut req: Request<Body> = Request::new(Post, url);
-        req.headers_mut().set(ContentType::form_url_encoded());
-        req.set_body(Body::from(self.form_encode()));
-
-        let mut dst = dst.lock().unwrap();
-        let work = client.request(req)
-            .and_then(|res| {
-                res.body().for_each(|chunk| {
-                    dst.extend_from_slice(&chunk);
-                    Ok(())
-                })
-            });
-        core.run(work).chain_err(|| "Failed to run registration")?;
+    /// Sets the URL to the homepage of the app.
+    pub fn website(mut self, website: &str) -> CreateApp {
+        self.website = Some(String::from(website));
+        self
+    }
+
+    /// Registers the app at `api_url`, blocking on the given `reactor`.
+    pub fn register(&self, api_url: &str, dst: Arc<Mutex<Vec<u8>>>, reactor: &Reactor) -> Result<()> {
+        let body = reactor.run(|http, _| post_form(api_url, self.form_encode(), http))
+            .chain_err(|| "Failed to run registration")?;
+        dst.lock().unwrap().extend_from_slice(&body);
         Ok(())
     }
 
+    /// Registers the app with the `http` client, resolving to the new `OAuthApp`.
+    pub fn register_async(&self, api_url: &str, http: &HttpsClient) -> Box<Future<Item = OAuthApp, Error = Error>> {
+        Box::new(post_form(api_url, self.form_encode(), http).and_then(|body| {
+            if body.is_empty() { bail!("Invalid result. Empty") }
+            serde_json::from_slice(&body).chain_err(|| "Unexpected JSON error.")
+        }))
+    }
+
     fn form_encode(&self) -> String {
-        form_urlencoded::Serializer::new(String::new())
-            .append_pair("client_name", &self.client_name)
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("client_name", &self.client_name)
             .append_pair("redirect_uris", &self.redirect_uris)
-            .append_pair("scopes", &self.scopes)
-            .finish()
+            .append_pair("scopes", &self.scopes);
+        if let Some(ref website) = self.website {
+            form.append_pair("website", website);
+        }
+        form.finish()
     }
 
 }
+
+/// Sends a form-encoded `body` to `url` with the `http` client, resolving to the body of the
+/// response.
+fn post_form(url: &str, body: String, http: &HttpsClient) -> Box<Future<Item = Vec<u8>, Error = Error>> {
+    let uri = match Uri::from_str(url) {
+        Ok(uri) => uri,
+        Err(e) => return Box::new(future::err(Error::with_chain(e, "Invalid OAuth URL")))
+    };
+    let mut req: Request<Body> = Request::new(Post, uri);
+    req.headers_mut().set(ContentType::form_url_encoded());
+    req.set_body(Body::from(body));
+
+    let work = http.request(req)
+        .and_then(|res| {
+            let status = res.status();
+            let headers = res.headers().clone();
+            res.body().concat2().map(move |body| (status, headers, body))
+        })
+        .map_err(Error::from)
+        .and_then(|(status, headers, body)| {
+            if !status.is_success() {
+                return Err(Error::from_response(status, &headers, &body));
+            }
+            Ok(body.to_vec())
+        });
+    Box::new(work)
+}
@@ -0,0 +1,427 @@
+//! This module contains the asynchronous API Client, which runs on a caller-provided event loop.
+//!
+use errors::*;
+use futures::{future, Future, Stream};
+use futures::future::Loop;
+use hyper::header::Headers;
+use reactor::{https_client, HttpsClient};
+use serde::{Deserialize, Serialize};
+use super::client::Client;
+use super::entities;
+use super::methods::{
+    AccountID,
+    APIEndpoint,
+    APIEndpointRequest,
+    HomeTimelineQuery,
+    ListID,
+    MEDIA_PROCESSING_ATTEMPTS,
+    MEDIA_PROCESSING_INTERVAL,
+    MediaID,
+    MediaUpload,
+    NewReport,
+    NewStatus,
+    NotificationID,
+    NotificationsQuery,
+    PageQuery,
+    RelationshipsQuery,
+    SearchAccountsQuery,
+    SearchContentsQuery,
+    SearchType,
+    StatusID,
+    TimelineQuery,
+    UserFormData
+};
+use super::pagination::Page;
+use super::streaming::{streaming_base_url, EventStream, StreamingTimeline};
+use std::str::FromStr;
+use tokio_core::reactor::{Handle, Timeout};
+use url::Url;
+use version::Capability;
+use webfinger::{self, Acct};
+
+/// A boxed future, resolving to a response from the Mastodon API.
+pub type FutureResponse<T> = Box<Future<Item = T, Error = Error>>;
+
+/// The asynchronous API Client.
+///
+/// A single HTTPS client, and its connection pool, is kept alive and shared by every request,
+/// which are run on the event loop of the `Handle` given to `AsyncClient::new`.
+///
+/// Each API method mirrors the operation of the same name in the `methods` traits.
+#[derive(Clone)]
+pub struct AsyncClient {
+    api: Client,
+    http: HttpsClient,
+    handle: Handle
+}
+
+impl AsyncClient {
+    /// Creates a new `AsyncClient`, building its requests with the given `Client`.
+    pub fn new(api: Client, handle: &Handle) -> AsyncClient {
+        AsyncClient::with_http(api, https_client(handle), handle)
+    }
+
+    /// Creates a new `AsyncClient`, sending its requests with an existing `HttpsClient`, and
+    /// sharing its connection pool.
+    pub fn with_http(api: Client, http: HttpsClient, handle: &Handle) -> AsyncClient {
+        AsyncClient { api, http, handle: handle.clone() }
+    }
+
+    /// Returns the `Client` used to build the requests.
+    pub fn api(&self) -> &Client {
+        &self.api
+    }
+
+    /// Returns the `HttpsClient` sending the requests, e.g. to share its connection pool.
+    pub fn http(&self) -> &HttpsClient {
+        &self.http
+    }
+
+    /// Sends the request for `endpoint`, resolving to the response headers and body.
+    ///
+    /// Non-success responses resolve to the matching API error.
+    pub fn send(&self, endpoint: APIEndpoint) -> FutureResponse<(Headers, Vec<u8>)> {
+        let req = match self.api.build_request(endpoint) {
+            Ok(req) => req,
+            Err(e) => return Box::new(future::err(e))
+        };
+        let work = self.http.request(req)
+            .and_then(|res| {
+                let status = res.status();
+                let headers = res.headers().clone();
+                res.body().concat2().map(move |body| (status, headers, body))
+            })
+            .map_err(|e| Error::with_chain(e, "Failed to run request"))
+            .and_then(|(status, headers, body)| {
+                if !status.is_success() {
+                    return Err(Error::from_response(status, &headers, &body));
+                }
+                Ok((headers, body.to_vec()))
+            });
+        Box::new(work)
+    }
+
+    /// Sends the request for `endpoint`, resolving to the JSON-decoded response.
+    ///
+    /// `Decoding::Strict` only rejects unknown values of string-typed fields here, since `T` can
+    /// not be encoded back to check its fields.
+    pub fn request<T: Deserialize + 'static>(&self, endpoint: APIEndpoint) -> FutureResponse<T> {
+        let decoding = self.api.decoding;
+        Box::new(self.send(endpoint).and_then(move |(_, body)| {
+            decoding.decode_any(&body).chain_err(|| "Unexpected JSON error.")
+        }))
+    }
+
+    /// Sends the request for an `endpoint` returning entities, checked field by field when decoding
+    /// strictly.
+    pub fn request_entity<T: Deserialize + Serialize + 'static>(&self, endpoint: APIEndpoint) -> FutureResponse<T> {
+        let decoding = self.api.decoding;
+        Box::new(self.send(endpoint).and_then(move |(_, body)| {
+            decoding.decode(&body).chain_err(|| "Unexpected JSON error.")
+        }))
+    }
+
+    /// Sends the request for a paginated `endpoint`, resolving to a single `Page` of results.
+    ///
+    /// `Decoding::Strict` only rejects unknown values of string-typed fields here; use
+    /// `request_entity_page` to also check the fields of the entities.
+    pub fn request_page<T: Deserialize + 'static>(&self, endpoint: APIEndpoint) -> FutureResponse<Page<T>> {
+        let decoding = self.api.decoding;
+        Box::new(self.send(endpoint).and_then(move |(headers, body)| {
+            let items = decoding.decode_any(&body).chain_err(|| "Unexpected JSON error fetching page.")?;
+            Ok(Page::new(items, &headers))
+        }))
+    }
+
+    /// Sends the request for a paginated `endpoint` returning entities, resolving to a single
+    /// `Page` of them, checked field by field when decoding strictly.
+    pub fn request_entity_page<T: Deserialize + Serialize + 'static>(&self, endpoint: APIEndpoint) -> FutureResponse<Page<T>> {
+        let decoding = self.api.decoding;
+        Box::new(self.send(endpoint).and_then(move |(headers, body)| {
+            let items = decoding.decode(&body).chain_err(|| "Unexpected JSON error fetching page.")?;
+            Ok(Page::new(items, &headers))
+        }))
+    }
+
+    // Accounts
+
+    pub fn fetch_account(&self, account_id: AccountID) -> FutureResponse<entities::Account> {
+        self.request_entity(APIEndpoint::FetchAccount(account_id))
+    }
+    pub fn get_current_user(&self) -> FutureResponse<entities::Account> {
+        self.request_entity(APIEndpoint::GetCurrentUser)
+    }
+    pub fn update_current_user(&self, form_data: UserFormData) -> FutureResponse<entities::Account> {
+        self.request_entity(APIEndpoint::UpdateCurrentUser(form_data))
+    }
+    pub fn get_account_followers(&self, account_id: AccountID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::GetAccountFollowers(account_id))
+    }
+    pub fn get_account_following(&self, account_id: AccountID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::GetFollowing(account_id))
+    }
+    pub fn get_account_statutes(&self, account_id: AccountID) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::GetAccountStatuses(account_id))
+    }
+    pub fn follow_account(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::FollowAccount(account_id))
+    }
+    pub fn unfollow_account(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::UnfollowAccount(account_id))
+    }
+    pub fn block_account(&self, account_id: AccountID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::BlockAccount(account_id))
+    }
+    pub fn unblock_account(&self, account_id: AccountID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::UnblockAccount(account_id))
+    }
+    pub fn mute_account(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::MuteAccount(account_id))
+    }
+    pub fn unmute_account(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::UnmuteAccount(account_id))
+    }
+    pub fn get_account_relationships(&self, query: RelationshipsQuery) -> FutureResponse<Vec<entities::Relationship>> {
+        self.request_entity(APIEndpoint::GetAccountRelationships(query))
+    }
+    pub fn search_accounts(&self, query: SearchAccountsQuery) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::SearchAccounts(query))
+    }
+
+    // Statuses
+
+    pub fn fetch_status(&self, status_id: StatusID) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::FetchStatus(status_id))
+    }
+    pub fn get_status_context(&self, status_id: StatusID) -> FutureResponse<entities::Context> {
+        self.request_entity(APIEndpoint::GetStatusContext(status_id))
+    }
+    pub fn get_status_card(&self, status_id: StatusID) -> FutureResponse<entities::Card> {
+        self.request_entity(APIEndpoint::GetStatusCard(status_id))
+    }
+    pub fn reblogged_by(&self, status_id: StatusID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::RebloggedBy(status_id))
+    }
+    pub fn favourited_by(&self, status_id: StatusID) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::FavouritedBy(status_id))
+    }
+    pub fn post_status(&self, new_status: NewStatus) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::PostStatus(new_status))
+    }
+    pub fn delete_status(&self, status_id: StatusID) -> FutureResponse<()> {
+        Box::new(self.send(APIEndpoint::DeleteStatus(status_id)).map(|_| ()))
+    }
+    pub fn reblog_status(&self, status_id: StatusID) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::ReblogStatus(status_id))
+    }
+    pub fn unreblog_status(&self, status_id: StatusID) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::UnreblogStatus(status_id))
+    }
+    pub fn favourite_status(&self, status_id: StatusID) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::FavouriteStatus(status_id))
+    }
+    pub fn unfavourite_status(&self, status_id: StatusID) -> FutureResponse<entities::Status> {
+        self.request_entity(APIEndpoint::UnfavouriteStatus(status_id))
+    }
+
+    // Timelines
+
+    pub fn home_timeline(&self, query: HomeTimelineQuery) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::HomeTimeline(query))
+    }
+    pub fn public_timeline(&self, query: TimelineQuery) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::PublicTimeline(query))
+    }
+    pub fn tag_timeline(&self, hashtag: &str, query: TimelineQuery) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::TagTimeline(String::from(hashtag), query))
+    }
+    pub fn list_timeline(&self, list_id: ListID, query: HomeTimelineQuery) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::ListTimeline(list_id, query))
+    }
+
+    // Notifications
+
+    pub fn fetch_notifications(&self, query: NotificationsQuery) -> FutureResponse<Vec<entities::Notification>> {
+        self.request_entity(APIEndpoint::FetchNotifications(query))
+    }
+    pub fn get_notification(&self, notification_id: NotificationID) -> FutureResponse<entities::Notification> {
+        self.request_entity(APIEndpoint::GetNotification(notification_id))
+    }
+    pub fn clear_notifications(&self) -> FutureResponse<()> {
+        Box::new(self.send(APIEndpoint::ClearNotifications).map(|_| ()))
+    }
+    pub fn dismiss_notification(&self, notification_id: NotificationID) -> FutureResponse<()> {
+        Box::new(self.send(APIEndpoint::DismissNotification(notification_id)).map(|_| ()))
+    }
+
+    // Blocks
+
+    pub fn fetch_blocks(&self, query: PageQuery) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::FetchBlocks(query))
+    }
+
+    // Favourites
+
+    pub fn fetch_favourites(&self, query: PageQuery) -> FutureResponse<Vec<entities::Status>> {
+        self.request_entity(APIEndpoint::FetchFavourites(query))
+    }
+
+    // Follows
+
+    /// Resolves a `user@domain` handle to the `AccountID` known to this instance.
+    pub fn resolve_account(&self, acct: &Acct) -> FutureResponse<Option<AccountID>> {
+        let query = SearchContentsQuery {
+            q: acct.to_string(),
+            search_type: Some(SearchType::Accounts),
+            resolve: Some(true),
+            ..SearchContentsQuery::default()
+        };
+        let acct = acct.clone();
+        let local_domain = String::from(self.api.url_base.host_str().unwrap_or(""));
+        let accounts: FutureResponse<Vec<entities::Account>> = if self.api.supports(Capability::SearchV2) {
+            Box::new(self.search_content_v2(query).map(|results| results.accounts))
+        } else {
+            Box::new(self.search_content(query).map(|results| results.accounts))
+        };
+        Box::new(accounts.map(move |accounts| {
+            accounts.into_iter()
+                .find(|account| acct.matches(&account.acct, &local_domain))
+                .map(|account| account.account_id)
+        }))
+    }
+    pub fn follow_remote_user(&self, acct: &str) -> FutureResponse<entities::Relationship> {
+        let acct = match Acct::from_str(acct) {
+            Ok(acct) => acct,
+            Err(e) => return Box::new(future::err(e))
+        };
+        let client = self.clone();
+        let work = self.resolve_account(&acct).and_then(move |account_id| -> FutureResponse<AccountID> {
+            if let Some(account_id) = account_id {
+                return Box::new(future::ok(account_id));
+            }
+            Box::new(webfinger::lookup_async(&acct, &client.http).and_then(move |jrd| {
+                let canonical = jrd.acct()
+                    .ok_or_else(|| Error::from(format!("No account handle for {}", acct)));
+                future::result(canonical).and_then(move |canonical| {
+                    client.resolve_account(&canonical).and_then(move |account_id| {
+                        account_id.ok_or_else(|| Error::from(format!("Could not resolve account {}", acct)))
+                    })
+                })
+            }))
+        });
+        let client = self.clone();
+        Box::new(work.and_then(move |account_id| client.follow_account(account_id)))
+    }
+
+    // Follow Requests
+
+    pub fn fetch_follow_requests(&self, query: PageQuery) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::FetchFollowRequests(query))
+    }
+    pub fn authorize_follow_request(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::AuthorizeFollowRequest(account_id))
+    }
+    pub fn reject_follow_request(&self, account_id: AccountID) -> FutureResponse<entities::Relationship> {
+        self.request_entity(APIEndpoint::RejectFollowRequest(account_id))
+    }
+
+    // Instances
+
+    pub fn get_instance(&self) -> FutureResponse<entities::Instance> {
+        self.request_entity(APIEndpoint::GetInstance)
+    }
+    pub fn get_instance_v2(&self) -> FutureResponse<entities::InstanceV2> {
+        self.request_entity(APIEndpoint::GetInstanceV2)
+    }
+
+    // Mutes
+
+    pub fn fetch_mutes(&self, query: PageQuery) -> FutureResponse<Vec<entities::Account>> {
+        self.request_entity(APIEndpoint::FetchMutes(query))
+    }
+
+    // Reports
+
+    pub fn fetching_reports(&self) -> FutureResponse<Vec<entities::Report>> {
+        self.request_entity(APIEndpoint::FetchReports)
+    }
+    pub fn reporting_user(&self, report: NewReport) -> FutureResponse<entities::Report> {
+        self.request_entity(APIEndpoint::ReportUser(report))
+    }
+
+    // Search
+
+    pub fn search_content(&self, query: SearchContentsQuery) -> FutureResponse<entities::Results> {
+        self.request_entity(APIEndpoint::SearchContent(query))
+    }
+    pub fn search_content_v2(&self, query: SearchContentsQuery) -> FutureResponse<entities::ResultsV2> {
+        self.request_entity(APIEndpoint::SearchContentV2(query))
+    }
+
+    // Media
+
+    pub fn upload_media(&self, media: MediaUpload) -> FutureResponse<entities::Attachment> {
+        self.request_entity(APIEndpoint::UploadMedia(media))
+    }
+    /// Uploads to `/api/v2/media`, resolving once the server has finished processing the media.
+    pub fn upload_media_v2(&self, media: MediaUpload) -> FutureResponse<entities::Attachment> {
+        let client = self.clone();
+        Box::new(self.request_entity(APIEndpoint::UploadMediaV2(media)).and_then(move |attachment| {
+            future::loop_fn((client, attachment, 0), |(client, attachment, attempts)| {
+                client.poll_media(attachment, attempts)
+            })
+        }))
+    }
+    pub fn get_media(&self, media_id: MediaID) -> FutureResponse<entities::Attachment> {
+        self.request_entity(APIEndpoint::GetMedia(media_id))
+    }
+
+    fn poll_media(self, attachment: entities::Attachment, attempts: usize)
+        -> FutureResponse<Loop<entities::Attachment, (AsyncClient, entities::Attachment, usize)>> {
+        if attachment.url.is_some() {
+            return Box::new(future::ok(Loop::Break(attachment)));
+        }
+        if attempts == MEDIA_PROCESSING_ATTEMPTS {
+            let e = format!("Media {} was not processed in time", attachment.attachment_id);
+            return Box::new(future::err(Error::from(e)));
+        }
+        let timeout = match Timeout::new(MEDIA_PROCESSING_INTERVAL, &self.handle) {
+            Ok(timeout) => timeout,
+            Err(e) => return Box::new(future::err(Error::from(e)))
+        };
+        let media_id = attachment.attachment_id.clone();
+        Box::new(timeout.map_err(Error::from).and_then(move |_| {
+            self.get_media(media_id).map(move |attachment| Loop::Continue((self, attachment, attempts + 1)))
+        }))
+    }
+
+    // Apps
+
+    pub fn verify_app_credentials(&self) -> FutureResponse<entities::Application> {
+        self.request_entity(APIEndpoint::VerifyAppCredentials)
+    }
+
+    // Streaming
+
+    /// Fetches the base URL of the Streaming API from the instance information.
+    ///
+    /// Set it with `Client::with_streaming_url` on the `Client` of a new `AsyncClient`, when the
+    /// instance streams from another host.
+    pub fn fetch_streaming_url(&self) -> FutureResponse<Url> {
+        let url_base = self.api.url_base.clone();
+        Box::new(self.get_instance().and_then(move |instance| {
+            match instance.streaming_api() {
+                Some(streaming_api) => streaming_base_url(streaming_api),
+                None => Ok(url_base)
+            }
+        }))
+    }
+
+    /// Opens a `Stream` of the events pushed for `timeline`, reconnecting as needed.
+    ///
+    /// Connects to the `streaming_url` of the `Client` when it is set, and otherwise follows the
+    /// redirects of the instance to its streaming host.
+    pub fn stream(&self, timeline: StreamingTimeline) -> Result<EventStream> {
+        EventStream::new(self.api.clone(), self.http.clone(), &self.handle, timeline)
+    }
+}
@@ -1,17 +1,20 @@
 //! This module contains the code representing Mastodon nodes and API Clients
 //!
+use api::oauth::{CreateApp, OAuthApp};
 use errors::*;
-use futures::{Future, Stream};
-use hyper::Client as WebClient;
 use hyper::{Body, Uri, Method};
-use hyper::Method::{Get, Patch, Post};
+use hyper::Method::{Delete, Get, Patch, Post};
 use hyper::client::Request;
-use hyper::header::{Authorization, Bearer};
-use hyper_tls::HttpsConnector;
+use hyper::header::{Authorization, Bearer, ContentType, Headers};
 use mastodon::ApiHandler;
-use serde_json;
+use reactor::Reactor;
+use serde::{Deserialize, Serialize};
+use serde_urlencoded;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use std::thread;
+use super::async_client::AsyncClient;
+use super::decoding::Decoding;
 use super::entities;
 use super::methods;
 use super::methods::{
@@ -19,23 +22,48 @@ use super::methods::{
     APIEndpoint,
     APIEndpointRequest,
     HomeTimelineQuery,
+    ListID,
+    MediaID,
+    MediaUpload,
+    NewReport,
+    NewStatus,
     NotificationID,
+    NotificationsQuery,
+    PageQuery,
     RelationshipsQuery,
     SearchAccountsQuery,
     SearchContentsQuery,
+    SearchType,
     StatusID,
-    TagID,
     TimelineQuery,
     UserFormData
 };
-use tokio_core::reactor::Core;
+use super::pagination::{Page, Pages};
+use super::streaming::{streaming_base_url, StreamingTimeline};
 use url::Url;
+use version::{Capability, Version};
+use webfinger::{self, Acct};
 
 
 /// The API Client, currently works for version 1 of the Mastodon API.
+///
+/// Every request blocks until its response arrives. The event loop and the pooled connections
+/// are kept alive between requests, and shared by the clones of a `Client`, which are thus bound
+/// to the thread that created them. See `AsyncClient` for running requests on your own event loop.
+///
+/// When the `version` of the instance is known, methods that it does not support fail with
+/// `ErrorKind::Unsupported` before any request is sent.
+///
+/// Responses are decoded leniently, unless `decoding` is set to `Decoding::Strict`.
+#[derive(Clone, Debug)]
 pub struct Client {
     pub url_base: Url,
-    pub token: Bearer
+    pub token: Bearer,
+    pub version: Option<Version>,
+    pub decoding: Decoding,
+    /// Base URL of the Streaming API, when served from another host than `url_base`.
+    pub streaming_url: Option<Url>,
+    reactor: Reactor
 }
 
 impl ApiHandler for Client {
@@ -44,122 +72,533 @@ impl ApiHandler for Client {
     }
 }
 
+/// Appends the `pairs` to the query of `url`, leaving it without a query when there are none.
+fn append_query(url: &mut Url, pairs: Vec<(&'static str, String)>) {
+    if !pairs.is_empty() {
+        url.query_pairs_mut().extend_pairs(pairs);
+    }
+}
+
+impl Client {
+    /// Returns the URL of `path`, followed by the percent-encoded `segments`, such as IDs.
+    ///
+    /// Empty segments are rejected, since an empty ID would address the parent collection.
+    fn segments_url(&self, path: &str, segments: &[&str]) -> Result<Url> {
+        if segments.iter().any(|segment| segment.is_empty()) {
+            bail!("Empty ID in endpoint path");
+        }
+        let mut url = self.endpoint_url(path)?;
+        url.path_segments_mut()
+            .map_err(|_| "invalid endpoint URL")?
+            .extend(segments);
+        Ok(url)
+    }
+
+    /// Returns the URL of a Streaming API endpoint, on the `streaming_url` when it is known.
+    fn streaming_endpoint_url(&self, path: &str) -> Result<Url> {
+        match self.streaming_url {
+            Some(ref base) => base.join(path).chain_err(|| "could not join path with streaming URL"),
+            None => self.endpoint_url(path)
+        }
+    }
+}
+
 impl APIEndpointRequest for Client {
+    /// Builds a request with the `Authorization` of the `token`, or none when it is empty.
     fn bearer_token_request(&self, method: Method, uri: Uri) -> Result<Request<Body>> {
         let mut req = Request::new(method, uri);
-        req.headers_mut().set(Authorization(self.token.clone()));
+        if !self.token.token.is_empty() {
+            req.headers_mut().set(Authorization(self.token.clone()));
+        }
         Ok(req)
     }
     fn build_request(&self, endpoint: APIEndpoint) -> Result<Request<Body>> {
+        if let (Some(capability), Some(version)) = (endpoint.capability(), self.version.as_ref()) {
+            version.require(capability)?;
+        }
         match endpoint {
             APIEndpoint::FetchAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str()])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::GetCurrentUser => {
                 let url = self.endpoint_url("/api/v1/accounts/verify_credentials")?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::UpdateCurrentUser(d) => {
                 let url = self.endpoint_url("/api/v1/accounts/update_credentials")?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
-                self.bearer_token_request(Patch, uri)
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let mut req = self.bearer_token_request(Patch, uri)?;
+                if d.has_images() {
+                    let form = d.multipart()?;
+                    req.headers_mut().set_raw("Content-Type", form.content_type());
+                    req.set_body(Body::from(form.finish()));
+                } else {
+                    req.headers_mut().set(ContentType::form_url_encoded());
+                    req.set_body(Body::from(d.form_encode()?));
+                }
+                Ok(req)
             },
             APIEndpoint::GetAccountFollowers(a) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/followers", a.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[a.as_str(), "followers"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::GetFollowing(a) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/following", a.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[a.as_str(), "following"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::GetAccountStatuses(a) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/statuses", a.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[a.as_str(), "statuses"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::FollowAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/follow", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "follow"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Post, uri)
             },
             APIEndpoint::UnfollowAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/unfollow", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "unfollow"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Post, uri)
             },
             APIEndpoint::BlockAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/block", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
-                Ok(Request::new(Post, uri))
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "block"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
             },
             APIEndpoint::UnblockAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/unblock", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
-                Ok(Request::new(Post, uri))
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "unblock"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
             },
             APIEndpoint::MuteAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/mute", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "mute"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Post, uri)
             },
             APIEndpoint::UnmuteAccount(account) => {
-                let url = self.endpoint_url(&format!("/api/v1/accounts/{}/unmute", account.id))?;
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let url = self.segments_url("/api/v1/accounts", &[account.as_str(), "unmute"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Post, uri)
             },
             APIEndpoint::GetAccountRelationships(query) => {
-                let mut url = self.endpoint_url("/api/v1/accounts/relationships").unwrap();
+                let mut url = self.endpoint_url("/api/v1/accounts/relationships")?;
                 match query {
                     RelationshipsQuery::SingleAccount(account) => {
-                        url.query_pairs_mut().append_pair("id", &format!("{}", account.id));
+                        url.query_pairs_mut().append_pair("id", account.as_str());
                     },
                     RelationshipsQuery::MultipleAccounts(accounts) => {
                         for account in &accounts {
                             url.query_pairs_mut()
-                                .append_pair("id[]", &format!("{}", account.id));
+                                .append_pair("id[]", account.as_str());
                         }
                     }
                 };
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             },
             APIEndpoint::SearchAccounts(q) => {
-                let mut url = self.endpoint_url("/api/v1/accounts/search").unwrap();
+                let mut url = self.endpoint_url("/api/v1/accounts/search")?;
                 url.query_pairs_mut().append_pair("q", &q.q);
                 if let Some(limit) = q.limit {
                     url.query_pairs_mut().append_pair("limit", &format!("{}", limit));
                 };
-                let uri = Uri::from_str(url.as_str()).unwrap();
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str()])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::GetStatusContext(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "context"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::GetStatusCard(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "card"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::RebloggedBy(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "reblogged_by"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FavouritedBy(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "favourited_by"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::PostStatus(new_status) => {
+                let form_data = new_status.form_encode()?;
+                let url = self.endpoint_url("/api/v1/statuses")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let mut req = self.bearer_token_request(Post, uri)?;
+                req.headers_mut().set(ContentType::form_url_encoded());
+                req.set_body(Body::from(form_data));
+                Ok(req)
+            },
+            APIEndpoint::DeleteStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str()])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Delete, uri)
+            },
+            APIEndpoint::ReblogStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "reblog"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::UnreblogStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "unreblog"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::FavouriteStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "favourite"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::UnfavouriteStatus(status) => {
+                let url = self.segments_url("/api/v1/statuses", &[status.as_str(), "unfavourite"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::HomeTimeline(query) => {
+                let mut url = self.endpoint_url("/api/v1/timelines/home")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::PublicTimeline(query) => {
+                let mut url = self.endpoint_url("/api/v1/timelines/public")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::TagTimeline(hashtag, query) => {
+                let mut url = self.endpoint_url("/api/v1/timelines/tag")?;
+                url.path_segments_mut()
+                    .map_err(|_| "invalid endpoint URL")?
+                    .push(hashtag.trim_left_matches('#'));
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::ListTimeline(list, query) => {
+                let mut url = self.segments_url("/api/v1/timelines/list", &[list.as_str()])?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchNotifications(query) => {
+                let mut url = self.endpoint_url("/api/v1/notifications")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::GetNotification(notification) => {
+                let url = self.segments_url("/api/v1/notifications", &[notification.as_str()])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::ClearNotifications => {
+                let url = self.endpoint_url("/api/v1/notifications/clear")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::DismissNotification(notification) => {
+                let url = self.endpoint_url("/api/v1/notifications/dismiss")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let form_data = serde_urlencoded::to_string(&[("id", notification.as_str())][..])?;
+                let mut req = self.bearer_token_request(Post, uri)?;
+                req.headers_mut().set(ContentType::form_url_encoded());
+                req.set_body(Body::from(form_data));
+                Ok(req)
+            },
+            APIEndpoint::FetchBlocks(query) => {
+                let mut url = self.endpoint_url("/api/v1/blocks")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchFavourites(query) => {
+                let mut url = self.endpoint_url("/api/v1/favourites")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchFollowRequests(query) => {
+                let mut url = self.endpoint_url("/api/v1/follow_requests")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::GetInstance => {
+                let url = self.endpoint_url("/api/v1/instance")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::GetInstanceV2 => {
+                let url = self.endpoint_url("/api/v2/instance")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchMutes(query) => {
+                let mut url = self.endpoint_url("/api/v1/mutes")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::AuthorizeFollowRequest(account) => {
+                let url = self.segments_url("/api/v1/follow_requests", &[account.as_str(), "authorize"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::RejectFollowRequest(account) => {
+                let url = self.segments_url("/api/v1/follow_requests", &[account.as_str(), "reject"])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Post, uri)
+            },
+            APIEndpoint::FetchReports => {
+                let url = self.endpoint_url("/api/v1/reports")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::ReportUser(report) => {
+                let form_data = report.form_encode()?;
+                let url = self.endpoint_url("/api/v1/reports")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let mut req = self.bearer_token_request(Post, uri)?;
+                req.headers_mut().set(ContentType::form_url_encoded());
+                req.set_body(Body::from(form_data));
+                Ok(req)
+            },
+            APIEndpoint::SearchContent(query) => {
+                let mut url = self.endpoint_url("/api/v1/search")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::SearchContentV2(query) => {
+                let mut url = self.endpoint_url("/api/v2/search")?;
+                append_query(&mut url, query.query_pairs());
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::UploadMedia(media) => {
+                let form = media.multipart()?;
+                let url = self.endpoint_url("/api/v1/media")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let mut req = self.bearer_token_request(Post, uri)?;
+                req.headers_mut().set_raw("Content-Type", form.content_type());
+                req.set_body(Body::from(form.finish()));
+                Ok(req)
+            },
+            APIEndpoint::UploadMediaV2(media) => {
+                let form = media.multipart()?;
+                let url = self.endpoint_url("/api/v2/media")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                let mut req = self.bearer_token_request(Post, uri)?;
+                req.headers_mut().set_raw("Content-Type", form.content_type());
+                req.set_body(Body::from(form.finish()));
+                Ok(req)
+            },
+            APIEndpoint::GetMedia(media) => {
+                let url = self.segments_url("/api/v1/media", &[media.as_str()])?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::VerifyAppCredentials => {
+                let url = self.endpoint_url("/api/v1/apps/verify_credentials")?;
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::FetchPage(url) => {
+                if url.origin() != self.url_base.origin() {
+                    bail!("Page URL is not on the Mastodon instance: {}", url);
+                }
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
+                self.bearer_token_request(Get, uri)
+            },
+            APIEndpoint::Streaming(timeline) => {
+                let url = match timeline {
+                    StreamingTimeline::User => self.streaming_endpoint_url("/api/v1/streaming/user")?,
+                    StreamingTimeline::Public => self.streaming_endpoint_url("/api/v1/streaming/public")?,
+                    StreamingTimeline::PublicLocal => self.streaming_endpoint_url("/api/v1/streaming/public/local")?,
+                    StreamingTimeline::Hashtag(hashtag) => {
+                        let mut url = self.streaming_endpoint_url("/api/v1/streaming/hashtag")?;
+                        url.query_pairs_mut().append_pair("tag", hashtag.trim_left_matches('#'));
+                        url
+                    },
+                    StreamingTimeline::List(list) => {
+                        let mut url = self.streaming_endpoint_url("/api/v1/streaming/list")?;
+                        url.query_pairs_mut().append_pair("list", list.as_str());
+                        url
+                    }
+                };
+                let uri = Uri::from_str(url.as_str()).chain_err(|| "Invalid endpoint URI")?;
                 self.bearer_token_request(Get, uri)
             }
         }
     }
 
-    fn send(&self, endpoint: APIEndpoint, dst: Arc<Mutex<Vec<u8>>>) -> Result<()> {
-        let mut core = Core::new().chain_err(|| "Could not start client reactor")?;
-        let client = WebClient::configure()
-            .connector(HttpsConnector::new(4, &core.handle()))
-            .build(&core.handle());
-
-        let req: Request<Body> = self.build_request(endpoint).chain_err(|| "Could not build request")?;
-        //req.headers_mut().set(ContentType::form_url_encoded());
-        //req.set_body(Body::from(self.form_encode()));
-
-        let mut dst = dst.lock().unwrap();
-        let work = client.request(req)
-            .and_then(|res| {
-                res.body().for_each(|chunk| {
-                    dst.extend_from_slice(&chunk);
-                    Ok(())
-                })
-            });
-        core.run(work).chain_err(|| "Failed to run registration")?;
-        Ok(())
+    fn send(&self, endpoint: APIEndpoint, dst: Arc<Mutex<Vec<u8>>>) -> Result<Headers> {
+        let (headers, body) = self.reactor.run(|http, handle| {
+            AsyncClient::with_http(self.clone(), http.clone(), handle).send(endpoint)
+        })?;
+        dst.lock().unwrap().extend_from_slice(&body);
+        Ok(headers)
+    }
+}
+
+impl Client {
+    /// Creates a new `Client` for the API at `url_base`, running its requests on `reactor`.
+    pub fn new(url_base: Url, token: Bearer, reactor: Reactor) -> Client {
+        Client {
+            url_base,
+            token,
+            version: None,
+            decoding: Decoding::default(),
+            streaming_url: None,
+            reactor
+        }
+    }
+
+    /// Sets the `Version` of the instance, used to fail fast on unsupported methods.
+    pub fn with_version(mut self, version: Version) -> Client {
+        self.version = Some(version);
+        self
+    }
+
+    /// Sets how the JSON responses are decoded, e.g. `Decoding::Strict` for conformance testing.
+    pub fn with_decoding(mut self, decoding: Decoding) -> Client {
+        self.decoding = decoding;
+        self
+    }
+
+    /// Sets the base URL of the Streaming API, e.g. as returned by `fetch_streaming_url`.
+    pub fn with_streaming_url(mut self, streaming_url: Url) -> Client {
+        self.streaming_url = Some(streaming_url);
+        self
+    }
+
+    /// Fetches the base URL of the Streaming API from the instance information.
+    ///
+    /// Falls back on `url_base` when the instance does not announce a streaming host.
+    pub fn fetch_streaming_url(&self) -> Result<Url> {
+        let instance = methods::Instances::get_instance(self)?;
+        match instance.streaming_api() {
+            Some(streaming_api) => streaming_base_url(streaming_api),
+            None => Ok(self.url_base.clone())
+        }
+    }
+
+    /// Returns whether the instance supports `capability`.
+    ///
+    /// Every capability is assumed to be supported when the `version` is unknown.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.version.as_ref().map_or(true, |version| version.supports(capability))
+    }
+
+    /// Resolves a `user@domain` handle to the `AccountID` known to this instance.
+    ///
+    /// Returns `None` when the instance can not find the account. Uses `/api/v1/search` on
+    /// instances without `Capability::SearchV2`.
+    pub fn resolve_account(&self, acct: &Acct) -> Result<Option<AccountID>> {
+        let query = SearchContentsQuery {
+            q: acct.to_string(),
+            search_type: Some(SearchType::Accounts),
+            resolve: Some(true),
+            ..SearchContentsQuery::default()
+        };
+        let accounts = if self.supports(Capability::SearchV2) {
+            methods::Search::search_content_v2(self, query)?.accounts
+        } else {
+            methods::Search::search_content(self, query)?.accounts
+        };
+        let local_domain = self.url_base.host_str().unwrap_or("");
+        let account = accounts.into_iter().find(|account| acct.matches(&account.acct, local_domain));
+        Ok(account.map(|account| account.account_id))
+    }
+
+    /// Fetches a single `Page` of results from a paginated endpoint.
+    ///
+    /// `Decoding::Strict` only rejects unknown values of string-typed fields here; use
+    /// `fetch_entity_page` to also check the fields of the entities.
+    pub fn fetch_page<T: Deserialize>(&self, endpoint: APIEndpoint) -> Result<Page<T>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let headers = self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        let items = self.decoding.decode_any(&data).chain_err(|| "Unexpected JSON error fetching page.")?;
+        Ok(Page::new(items, &headers))
+    }
+
+    /// Fetches a single `Page` of entities from a paginated endpoint, checked field by field
+    /// when decoding strictly.
+    pub fn fetch_entity_page<T: Deserialize + Serialize>(&self, endpoint: APIEndpoint) -> Result<Page<T>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let headers = self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        let items = self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching page.")?;
+        Ok(Page::new(items, &headers))
+    }
+
+    /// Returns a lazy iterator over all the items of a paginated endpoint, up to `cap` items.
+    pub fn pages<T: Deserialize>(&self, endpoint: APIEndpoint, cap: Option<usize>) -> Pages<T> {
+        Pages::new(self, endpoint, cap)
+    }
+
+    /// Returns a lazy iterator over all the entities of a paginated endpoint, up to `cap` items,
+    /// fetching each page with `fetch_entity_page`.
+    pub fn entity_pages<T: Deserialize + Serialize>(&self, endpoint: APIEndpoint, cap: Option<usize>) -> Pages<T> {
+        Pages::with_fetch(self, endpoint, cap, Client::fetch_entity_page)
+    }
+
+    /// Iterates over all of an account's followers.
+    pub fn iter_account_followers(&self, account_id: AccountID, cap: Option<usize>) -> Pages<entities::Account> {
+        self.entity_pages(APIEndpoint::GetAccountFollowers(account_id), cap)
+    }
+
+    /// Iterates over all the accounts an account is following.
+    pub fn iter_account_following(&self, account_id: AccountID, cap: Option<usize>) -> Pages<entities::Account> {
+        self.entity_pages(APIEndpoint::GetFollowing(account_id), cap)
+    }
+
+    /// Iterates over all of an account's statuses.
+    pub fn iter_account_statuses(&self, account_id: AccountID, cap: Option<usize>) -> Pages<entities::Status> {
+        self.entity_pages(APIEndpoint::GetAccountStatuses(account_id), cap)
+    }
+
+    /// Iterates over all of the authenticated user's notifications.
+    pub fn iter_notifications(&self, query: NotificationsQuery, cap: Option<usize>) -> Pages<entities::Notification> {
+        self.entity_pages(APIEndpoint::FetchNotifications(query), cap)
+    }
+
+    /// Iterates over all the accounts which have requested to follow the authenticated user.
+    pub fn iter_follow_requests(&self, cap: Option<usize>) -> Pages<entities::Account> {
+        self.entity_pages(APIEndpoint::FetchFollowRequests(PageQuery::default()), cap)
+    }
+
+    /// Iterates over all the accounts blocked by the authenticated user.
+    pub fn iter_blocks(&self, cap: Option<usize>) -> Pages<entities::Account> {
+        self.entity_pages(APIEndpoint::FetchBlocks(PageQuery::default()), cap)
+    }
+
+    /// Iterates over all the accounts muted by the authenticated user.
+    pub fn iter_mutes(&self, cap: Option<usize>) -> Pages<entities::Account> {
+        self.entity_pages(APIEndpoint::FetchMutes(PageQuery::default()), cap)
+    }
+
+    /// Iterates over all the statuses favourited by the authenticated user.
+    pub fn iter_favourites(&self, cap: Option<usize>) -> Pages<entities::Status> {
+        self.entity_pages(APIEndpoint::FetchFavourites(PageQuery::default()), cap)
     }
 }
 
@@ -167,219 +606,410 @@ impl methods::Accounts for Client {
     fn fetch_account(&self, account_id: AccountID) -> Result<entities::Account> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::FetchAccount(account_id);
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error fetching account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching account.")
     }
     fn get_current_user(&self) -> Result<entities::Account> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::GetCurrentUser;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting current user.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting current user.")
     }
     fn update_current_user(&self, form_data: UserFormData) -> Result<entities::Account> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::UpdateCurrentUser(form_data);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error updating current user.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error updating current user.")
     }
     fn get_account_followers(&self, account_id: AccountID) -> Result<Vec<entities::Account>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::GetAccountFollowers(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting account followers.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting account followers.")
     }
     fn get_account_following(&self, account_id: AccountID) -> Result<Vec<entities::Account>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::GetFollowing(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting account's following.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting account's following.")
     }
     fn get_account_statutes(&self, account_id: AccountID) -> Result<Vec<entities::Status>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::GetAccountStatuses(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting account's statuses.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting account's statuses.")
     }
     fn follow_account(&self, account_id: AccountID) -> Result<entities::Relationship> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::FollowAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error following account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error following account.")
     }
     fn unfollow_account(&self, account_id: AccountID) -> Result<entities::Relationship> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::UnfollowAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error unfollowing account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error unfollowing account.")
     }
     fn block_account(&self, account_id: AccountID) -> Result<Vec<entities::Account>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::BlockAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error blocking account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error blocking account.")
     }
     fn unblock_account(&self, account_id: AccountID) -> Result<Vec<entities::Account>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::UnblockAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error unblocking account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error unblocking account.")
     }
     fn mute_account(&self, account_id: AccountID) -> Result<entities::Relationship> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::MuteAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error muting account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error muting account.")
     }
     fn unmute_account(&self, account_id: AccountID) -> Result<entities::Relationship> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::UnmuteAccount(account_id);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error unmuting account.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error unmuting account.")
     }
     fn get_account_relationships(&self, query: RelationshipsQuery) -> Result<Vec<entities::Relationship>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::GetAccountRelationships(query);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting account relationships.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting account relationships.")
     }
     fn search_accounts(&self, query: SearchAccountsQuery) -> Result<Vec<entities::Account>> {
         let data = Arc::new(Mutex::new(Vec::new()));
         let endpoint = APIEndpoint::SearchAccounts(query);;
-        self.send(endpoint, data.clone()).unwrap();
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting account search.")
+    }
+}
+impl methods::Apps for Client {
+    fn register_app(&self, name: &str, uris: &str, scopes: &str, website: &str) -> Result<OAuthApp> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let app = CreateApp::new(name, uris, scopes).website(website);
+        let url = self.endpoint_url("/api/v1/apps")?;
+        app.register(url.as_str(), data.clone()).chain_err(|| "Could not register App.")?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error registering app.")
+    }
+    fn verify_app_credentials(&self) -> Result<entities::Application> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::VerifyAppCredentials;
+        self.send(endpoint, data.clone())?;
         let data = data.lock().unwrap();
-        serde_json::from_slice(&data).chain_err(|| "Unexpected JSON error getting account search.")
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error verifying app credentials.")
     }
 }
 impl methods::Blocks for Client {
-    fn fetch_blocks(&self, query: String) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+    fn fetch_blocks(&self, query: PageQuery) -> Result<Vec<entities::Account>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchBlocks(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching blocks.")
     }
 }
 impl methods::Favourites for Client {
-    fn fetch_favourites(&self, query: String) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+    fn fetch_favourites(&self, query: PageQuery) -> Result<Vec<entities::Status>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchFavourites(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching favourites.")
     }
 }
 impl methods::FollowRequests for Client {
-    fn fetch_follow_requests(&self, query: String) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+    fn fetch_follow_requests(&self, query: PageQuery) -> Result<Vec<entities::Account>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchFollowRequests(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching follow requests.")
     }
-    fn authorize_follow_request(&self, form_data: String) -> Result<()> {
-        unimplemented!()
+    fn authorize_follow_request(&self, account_id: AccountID) -> Result<entities::Relationship> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::AuthorizeFollowRequest(account_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error authorizing follow request.")
     }
-    fn reject_follow_request(&self, form_data: String) -> Result<()> {
-        unimplemented!()
+    fn reject_follow_request(&self, account_id: AccountID) -> Result<entities::Relationship> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::RejectFollowRequest(account_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error rejecting follow request.")
     }
 }
 
 impl methods::Follows for Client {
-    fn follow_remote_user(&self, form_data: String) -> Result<entities::Account> {
-        unimplemented!()
+    fn follow_remote_user(&self, acct: &str) -> Result<entities::Relationship> {
+        let acct = Acct::from_str(acct)?;
+        let account_id = match self.resolve_account(&acct)? {
+            Some(account_id) => account_id,
+            None => {
+                let jrd = webfinger::lookup(&acct, &self.reactor)?;
+                let canonical = jrd.acct()
+                    .ok_or_else(|| Error::from(format!("No account handle for {}", acct)))?;
+                self.resolve_account(&canonical)?
+                    .ok_or_else(|| Error::from(format!("Could not resolve account {}", acct)))?
+            }
+        };
+        methods::Accounts::follow_account(self, account_id)
     }
 }
 
 impl methods::Instances for Client {
     fn get_instance(&self) -> Result<entities::Instance> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetInstance;
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting instance.")
+    }
+    fn get_instance_v2(&self) -> Result<entities::InstanceV2> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetInstanceV2;
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting instance.")
     }
 }
 
 impl methods::Media for Client {
-    fn upload_media(&self, form_data: String) -> Result<entities::Attachment> {
-        unimplemented!()
+    fn upload_media(&self, media: MediaUpload) -> Result<entities::Attachment> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::UploadMedia(media);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error uploading media.")
+    }
+    fn upload_media_v2(&self, media: MediaUpload) -> Result<entities::Attachment> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::UploadMediaV2(media);
+        self.send(endpoint, data.clone())?;
+        let mut attachment: entities::Attachment = {
+            let data = data.lock().unwrap();
+            self.decoding.decode(&data).chain_err(|| "Unexpected JSON error uploading media.")?
+        };
+        let mut attempts = 0;
+        while attachment.url.is_none() {
+            if attempts == methods::MEDIA_PROCESSING_ATTEMPTS {
+                bail!("Media {} was not processed in time", attachment.attachment_id);
+            }
+            thread::sleep(methods::MEDIA_PROCESSING_INTERVAL);
+            attempts += 1;
+            attachment = self.get_media(attachment.attachment_id.clone())?;
+        }
+        Ok(attachment)
+    }
+    fn get_media(&self, media_id: MediaID) -> Result<entities::Attachment> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetMedia(media_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching media.")
     }
 }
 
 impl methods::Mutes for Client {
-    fn fetch_mutes(&self, query: String) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+    fn fetch_mutes(&self, query: PageQuery) -> Result<Vec<entities::Account>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchMutes(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching mutes.")
     }
 }
 
 impl methods::Notifications for Client {
-    fn fetch_notifications(&self, query: String) -> Result<Vec<entities::Notification>> {
-        unimplemented!()
+    fn fetch_notifications(&self, query: NotificationsQuery) -> Result<Vec<entities::Notification>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchNotifications(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching notifications.")
     }
     fn get_notification(&self, notification_id: NotificationID) -> Result<entities::Notification> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetNotification(notification_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting notification.")
     }
     fn clear_notifications(&self) -> Result<()> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::ClearNotifications;
+        self.send(endpoint, data.clone()).chain_err(|| "Could not clear notifications.")?;
+        Ok(())
+    }
+    fn dismiss_notification(&self, notification_id: NotificationID) -> Result<()> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::DismissNotification(notification_id);
+        self.send(endpoint, data.clone()).chain_err(|| "Could not dismiss notification.")?;
+        Ok(())
     }
 }
 
 impl methods::Reports for Client {
     fn fetching_reports(&self) -> Result<Vec<entities::Report>> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchReports;
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching reports.")
     }
-    fn reporting_user(&self, form_data: String) -> Result<entities::Report> {
-        unimplemented!()
+    fn reporting_user(&self, report: NewReport) -> Result<entities::Report> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::ReportUser(report);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error reporting user.")
     }
 }
 
 impl methods::Search for Client {
     fn search_content(&self, query: SearchContentsQuery) -> Result<entities::Results> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::SearchContent(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error searching content.")
+    }
+    fn search_content_v2(&self, query: SearchContentsQuery) -> Result<entities::ResultsV2> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::SearchContentV2(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error searching content.")
     }
 }
 
 impl methods::Statuses for Client {
     fn fetch_status(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FetchStatus(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error fetching status.")
     }
-    fn get_status_context(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+    fn get_status_context(&self, status_id: StatusID) -> Result<entities::Context> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetStatusContext(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting status context.")
     }
     fn get_status_card(&self, status_id: StatusID) -> Result<entities::Card> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::GetStatusCard(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting status card.")
     }
     fn reblogged_by(&self, status_id: StatusID) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::RebloggedBy(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting who reblogged status.")
     }
     fn favourited_by(&self, status_id: StatusID) -> Result<Vec<entities::Account>> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FavouritedBy(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting who favourited status.")
     }
-    fn post_status(&self, form_data: String) -> Result<entities::Status> {
-        unimplemented!()
+    fn post_status(&self, new_status: NewStatus) -> Result<entities::Status> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::PostStatus(new_status);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error posting status.")
     }
     fn delete_status(&self, status_id: StatusID) -> Result<()> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::DeleteStatus(status_id);
+        self.send(endpoint, data.clone()).chain_err(|| "Could not delete status.")?;
+        Ok(())
     }
     fn reblog_status(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::ReblogStatus(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error reblogging status.")
     }
     fn unreblog_status(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::UnreblogStatus(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error unreblogging status.")
     }
     fn favourite_status(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::FavouriteStatus(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error favouriting status.")
     }
     fn unfavourite_status(&self, status_id: StatusID) -> Result<entities::Status> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::UnfavouriteStatus(status_id);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error unfavouriting status.")
     }
 }
 
 impl methods::Timelines for Client {
     fn home_timeline(&self, query: HomeTimelineQuery) -> Result<Vec<entities::Status>> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::HomeTimeline(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting home timeline.")
     }
     fn public_timeline(&self, query: TimelineQuery) -> Result<Vec<entities::Status>> {
-        unimplemented!()
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::PublicTimeline(query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting public timeline.")
+    }
+    fn tag_timeline(&self, hashtag: &str, query: TimelineQuery) -> Result<Vec<entities::Status>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::TagTimeline(String::from(hashtag), query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting tag timeline.")
     }
-    fn tag_timeline(&self, hashtag: TagID, query: TimelineQuery) -> Result<Vec<entities::Status>> {
-        unimplemented!()
+    fn list_timeline(&self, list_id: ListID, query: HomeTimelineQuery) -> Result<Vec<entities::Status>> {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let endpoint = APIEndpoint::ListTimeline(list_id, query);
+        self.send(endpoint, data.clone())?;
+        let data = data.lock().unwrap();
+        self.decoding.decode(&data).chain_err(|| "Unexpected JSON error getting list timeline.")
     }
 }
@@ -0,0 +1,137 @@
+//! This module contains the decoding of JSON responses into the Mastodon API entities.
+//!
+//! Decoding is lenient by default: the non-essential fields of an entity take their default
+//! value when they are missing or `null`, and unknown values of string-typed fields decode to
+//! their `Other` fallback. `Decoding::Strict` rejects those responses instead, as well as the
+//! fields unknown to the entities, which is useful for conformance testing against a server.
+//!
+use errors::*;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{self, Value};
+use std::cell::Cell;
+
+thread_local!(static STRICT: Cell<bool> = Cell::new(false));
+
+/// How the JSON responses are decoded into entities.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decoding {
+    /// Default the non-essential fields, and fall back on unknown values.
+    Lenient,
+    /// Reject the responses that do not match the entities exactly.
+    Strict
+}
+
+impl Default for Decoding {
+    fn default() -> Decoding {
+        Decoding::Lenient
+    }
+}
+
+impl Decoding {
+    /// Decodes a JSON `body` into `T`.
+    ///
+    /// In `Decoding::Strict` mode, the decoded `T` is encoded back to check that no field of
+    /// `body` was unknown, missing or `null`, hence the `Serialize` bound.
+    pub fn decode<T: Deserialize + Serialize>(&self, body: &[u8]) -> Result<T> {
+        match *self {
+            Decoding::Lenient => self.decode_any(body),
+            Decoding::Strict => decode_strict(body)
+        }
+    }
+
+    /// Decodes a JSON `body` into any deserializable `T`, such as the items of a generic page.
+    ///
+    /// `Decoding::Strict` only rejects the unknown values of string-typed fields here, since the
+    /// fields of `body` can not be checked without encoding `T` back, as `decode` does.
+    pub fn decode_any<T: Deserialize>(&self, body: &[u8]) -> Result<T> {
+        match *self {
+            Decoding::Lenient => serde_json::from_slice(body).chain_err(|| "Could not decode JSON"),
+            Decoding::Strict => {
+                let _strict = StrictGuard::new();
+                serde_json::from_slice(body).chain_err(|| "Could not decode JSON strictly")
+            }
+        }
+    }
+}
+
+/// Returns whether the current thread is decoding in `Decoding::Strict` mode.
+///
+/// Deserializers with a fallback value use it to reject unknown values instead.
+pub fn is_strict() -> bool {
+    STRICT.with(|strict| strict.get())
+}
+
+/// Deserializes a non-essential field, taking its default value on `null`.
+///
+/// Use with `#[serde(default, deserialize_with = "decoding::nullable")]`, to also default the
+/// field when it is missing.
+pub fn nullable<T, D>(deserializer: D) -> ::std::result::Result<T, D::Error>
+    where T: Default + Deserialize,
+          D: Deserializer
+{
+    Option::<T>::deserialize(deserializer).map(|value| value.unwrap_or_default())
+}
+
+/// Marks the current thread as decoding strictly, until dropped.
+struct StrictGuard;
+
+impl StrictGuard {
+    fn new() -> StrictGuard {
+        STRICT.with(|strict| strict.set(true));
+        StrictGuard
+    }
+}
+
+impl Drop for StrictGuard {
+    fn drop(&mut self) {
+        STRICT.with(|strict| strict.set(false));
+    }
+}
+
+fn decode_strict<T: Deserialize + Serialize>(body: &[u8]) -> Result<T> {
+    let received: Value = serde_json::from_slice(body).chain_err(|| "Could not decode JSON")?;
+    let decoded: T = {
+        let _strict = StrictGuard::new();
+        serde_json::from_value(received.clone()).chain_err(|| "Could not decode JSON strictly")?
+    };
+    let encoded = serde_json::to_value(&decoded).chain_err(|| "Could not encode decoded JSON")?;
+    conform(&received, &encoded, "")?;
+    Ok(decoded)
+}
+
+/// Checks that the fields of the `received` JSON and those of the `decoded` entity match, so that
+/// no field was unknown, nor took a default value.
+fn conform(received: &Value, decoded: &Value, path: &str) -> Result<()> {
+    match (received, decoded) {
+        (&Value::Object(ref received), &Value::Object(ref decoded)) => {
+            for key in received.keys() {
+                if !decoded.contains_key(key) {
+                    bail!("Unknown field: {}", field_path(path, key));
+                }
+            }
+            for (key, value) in decoded.iter() {
+                let field = field_path(path, key);
+                match received.get(key) {
+                    Some(received) if !received.is_null() => conform(received, value, &field)?,
+                    _ if !value.is_null() => bail!("Missing field: {}", field),
+                    _ => {}
+                }
+            }
+        },
+        (&Value::Array(ref received), &Value::Array(ref decoded)) => {
+            for (i, (received, decoded)) in received.iter().zip(decoded.iter()).enumerate() {
+                conform(received, decoded, &format!("{}[{}]", path, i))?;
+            }
+        },
+        _ => {}
+    }
+    Ok(())
+}
+
+fn field_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        String::from(key)
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
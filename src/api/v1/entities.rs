@@ -1,20 +1,121 @@
 //! This module contains the Mastodon API Entities `/api/v1`.
 //!
-#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+use chrono::{DateTime, UTC};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use super::decoding;
+use super::ids::{AccountID, MediaID, NotificationID, PollID, ReportID, StatusID};
+
+/// Defines an enum of the values of a string-typed field, with an `Other` fallback for the
+/// values added by newer versions of the API. The fallback is rejected by strict decoding.
+macro_rules! string_enum {
+    (pub enum $name:ident { $($variant:ident => $value:tt),* }) => {
+        #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+        pub enum $name {
+            $($variant,)*
+            Other(String)
+        }
+
+        impl $name {
+            /// Returns the name of the value, as used by the API.
+            pub fn as_str(&self) -> &str {
+                match *self {
+                    $($name::$variant => $value,)*
+                    $name::Other(ref other) => other.as_str()
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(value: &'a str) -> $name {
+                match value {
+                    $($value => $name::$variant,)*
+                    other => $name::Other(String::from(other))
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl Deserialize for $name {
+            fn deserialize<D: Deserializer>(deserializer: D) -> Result<$name, D::Error> {
+                let value = deserializer.deserialize_str(StrVisitor)?;
+                match $name::from(value.as_str()) {
+                    $name::Other(ref other) if decoding::is_strict() => {
+                        Err(de::Error::custom(format!("unknown {} value: {}", stringify!($name), other)))
+                    },
+                    value => Ok(value)
+                }
+            }
+        }
+    }
+}
+
+/// Visits the value of a string-typed field.
+struct StrVisitor;
+
+impl de::Visitor for StrVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(String::from(v))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<String, E> {
+        Ok(v)
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Account {
     #[serde(rename = "id")]
-    pub account_id: i32,
+    pub account_id: AccountID,
     pub username: String,
     pub acct: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub display_name: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub note: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub url: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub avatar: String,
+    pub avatar_static: Option<String>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub header: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub locked: bool,
-    pub created_at: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub emojis: Vec<Emoji>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub fields: Vec<Field>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub bot: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub group: bool,
+    pub moved: Option<Box<Account>>,
+    pub discoverable: Option<bool>,
+    pub created_at: DateTime<UTC>,
+    pub last_status_at: Option<String>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub followers_count: i32,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub following_count: i32,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub statuses_count: i32
 }
 
@@ -27,46 +128,190 @@ pub struct Application {
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Attachment {
     #[serde(rename = "id")]
-    pub attachment_id: i32,
+    pub attachment_id: MediaID,
     #[serde(rename = "type")]
-        pub attachment_type: String,
-        pub url: String,
-        pub remote_url: String,
-        pub preview_url: String,
-        pub text_url: String
+        pub attachment_type: AttachmentType,
+        pub url: Option<String>,
+        pub remote_url: Option<String>,
+        pub preview_url: Option<String>,
+        pub text_url: Option<String>,
+        pub description: Option<String>
+}
+
+string_enum! {
+    pub enum AttachmentType {
+        Image => "image",
+        Video => "video",
+        Gifv => "gifv",
+        Audio => "audio",
+        Unknown => "unknown"
+    }
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Card {
     pub url: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub title: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub description: String,
-    pub image: String,
+    pub image: Option<String>
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Context {
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub ancestors: Vec<Status>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub descendants: Vec<Status>
 }
 
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Emoji {
+    pub shortcode: String,
+    pub url: String,
+    pub static_url: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub visible_in_picker: bool,
+    pub category: Option<String>
+}
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Error {
-    pub error: String
+    pub error: String,
+    pub error_description: Option<String>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub verified_at: Option<DateTime<UTC>>
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Instance {
-    pub url: String,
+    pub uri: String,
     pub title: String,
+    pub short_description: Option<String>,
     pub description: String,
-    pub email: String
+    pub email: String,
+    pub version: String,
+    pub urls: Option<InstanceUrls>,
+    pub stats: Option<InstanceStats>,
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    pub registrations: Option<bool>,
+    pub approval_required: Option<bool>,
+    pub configuration: Option<InstanceConfiguration>,
+    pub contact_account: Option<Account>,
+    #[serde(default)]
+    pub rules: Vec<Rule>
+}
+
+impl Instance {
+    /// Returns the base URL of the Streaming API, which may be on another host.
+    pub fn streaming_api(&self) -> Option<&str> {
+        self.urls.as_ref().map(|urls| urls.streaming_api.as_str())
+    }
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct InstanceConfiguration {
+    pub urls: Option<InstanceConfigurationUrls>,
+    pub statuses: Option<StatusesConfiguration>,
+    pub media_attachments: Option<MediaConfiguration>,
+    pub polls: Option<PollsConfiguration>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceConfigurationUrls {
+    pub streaming: String
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceContact {
+    pub email: String,
+    pub account: Option<Account>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceRegistrations {
+    pub enabled: bool,
+    pub approval_required: bool,
+    pub message: Option<String>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceStats {
+    pub user_count: u64,
+    pub status_count: u64,
+    pub domain_count: u64
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceThumbnail {
+    pub url: String
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceUrls {
+    pub streaming_api: String
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceUsage {
+    pub users: InstanceUsers
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceUsers {
+    pub active_month: u64
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct InstanceV2 {
+    pub domain: String,
+    pub title: String,
+    pub version: String,
+    pub source_url: Option<String>,
+    pub description: String,
+    pub usage: Option<InstanceUsage>,
+    pub thumbnail: Option<InstanceThumbnail>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub configuration: InstanceConfiguration,
+    pub registrations: Option<InstanceRegistrations>,
+    pub contact: Option<InstanceContact>,
+    #[serde(default)]
+    pub rules: Vec<Rule>
+}
+
+impl InstanceV2 {
+    /// Returns the base URL of the Streaming API, which may be on another host.
+    pub fn streaming_api(&self) -> Option<&str> {
+        self.configuration.urls.as_ref().map(|urls| urls.streaming.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct MediaConfiguration {
+    #[serde(default)]
+    pub supported_mime_types: Vec<String>,
+    pub image_size_limit: u64,
+    pub image_matrix_limit: u64,
+    pub video_size_limit: u64,
+    pub video_frame_rate_limit: u64,
+    pub video_matrix_limit: u64
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Mention {
     #[serde(rename = "id")]
-    pub mention_id: i32,
+    pub mention_id: AccountID,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub url: String,
     pub username: String,
     pub acct: String
@@ -75,66 +320,182 @@ pub struct Mention {
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Notification {
     #[serde( rename = "id")]
-    pub notification_id: i32,
+    pub notification_id: NotificationID,
     #[serde( rename = "type")]
-        pub notification_type: String,
-        pub created_at: String,
+        pub notification_type: NotificationType,
+        pub created_at: DateTime<UTC>,
         pub account: Account,
         pub status: Option<Status>
 }
 
+string_enum! {
+    pub enum NotificationType {
+        Mention => "mention",
+        Reblog => "reblog",
+        Favourite => "favourite",
+        Follow => "follow",
+        FollowRequest => "follow_request",
+        Poll => "poll",
+        Status => "status",
+        Update => "update"
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Poll {
+    #[serde(rename = "id")]
+    pub poll_id: PollID,
+    pub expires_at: Option<DateTime<UTC>>,
+    pub expired: bool,
+    pub multiple: bool,
+    pub votes_count: u64,
+    pub voters_count: Option<u64>,
+    pub options: Vec<PollOption>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub emojis: Vec<Emoji>,
+    pub voted: Option<bool>,
+    pub own_votes: Option<Vec<usize>>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct PollOption {
+    pub title: String,
+    pub votes_count: Option<u64>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct PollsConfiguration {
+    pub max_options: u32,
+    pub max_characters_per_option: u32,
+    pub min_expiration: u64,
+    pub max_expiration: u64
+}
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Relationship {
     #[serde(rename = "id")]
-    pub relationship_id: i32,
+    pub relationship_id: AccountID,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub following: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub followed_by: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub blocking: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub muting: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub requested: bool
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Report {
     #[serde(rename = "id")]
-    pub report_id: i32,
-    pub action_taken: String,
+    pub report_id: ReportID,
+    pub action_taken: bool,
+    pub action_taken_at: Option<DateTime<UTC>>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub category: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub comment: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub forwarded: bool,
+    pub created_at: Option<DateTime<UTC>>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub status_ids: Vec<StatusID>,
+    pub target_account: Option<Account>
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Results {
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub accounts: Vec<Account>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub statuses: Vec<Status>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub hashtags: Vec<String>
 }
 
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ResultsV2 {
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub accounts: Vec<Account>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub statuses: Vec<Status>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub hashtags: Vec<Tag>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Rule {
+    pub id: String,
+    pub text: String
+}
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Status {
     #[serde(rename = "id")]
-    pub status_id: i32,
+    pub status_id: StatusID,
     pub uri: String,
-    pub url: String,
+    pub url: Option<String>,
     pub account: Account,
-    pub in_reply_to_id: Option<i32>,
-    pub in_reply_to_account_id: Option<i32>,
+    pub in_reply_to_id: Option<StatusID>,
+    pub in_reply_to_account_id: Option<AccountID>,
     pub reblog: Option<Box<Status>>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub content: String,
-    pub created_at: String,
+    pub created_at: DateTime<UTC>,
+    pub edited_at: Option<DateTime<UTC>>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub replies_count: i32,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub reblogs_count: i32,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub favourites_count: i32,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub reblogged: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub favourited: bool,
+    pub muted: Option<bool>,
+    pub bookmarked: Option<bool>,
+    pub pinned: Option<bool>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub sensitive: bool,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub spoiler_text: String,
-    pub visibility: String,
+    pub visibility: Visibility,
+    pub language: Option<String>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub media_attachments: Vec<Attachment>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub mentions: Vec<Mention>,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub tags: Vec<Tag>,
-    pub application: Application
+    #[serde(default, deserialize_with = "decoding::nullable")]
+    pub emojis: Vec<Emoji>,
+    pub poll: Option<Poll>,
+    pub card: Option<Card>,
+    pub application: Option<Application>
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct StatusesConfiguration {
+    pub max_characters: u32,
+    pub max_media_attachments: u32,
+    pub characters_reserved_per_url: u32
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Tag {
     pub name: String,
+    #[serde(default, deserialize_with = "decoding::nullable")]
     pub url: String
 }
+
+string_enum! {
+    pub enum Visibility {
+        Public => "public",
+        Unlisted => "unlisted",
+        Private => "private",
+        Direct => "direct"
+    }
+}
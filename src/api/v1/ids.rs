@@ -0,0 +1,136 @@
+//! This module contains the typed IDs of the Mastodon API entities.
+//!
+//! Mastodon serializes IDs as strings, while older instances and some other servers send them
+//! as numbers. Every ID is kept as a `String`, and is deserialized from either form.
+//!
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! string_id {
+    ($(#[$attr:meta])* pub struct $name:ident;) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new<T: Into<String>>(id: T) -> $name {
+                $name(id.into())
+            }
+
+            /// Returns the ID as sent by the server.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> $name {
+                $name(id)
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(id: &'a str) -> $name {
+                $name(String::from(id))
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> $name {
+                $name(id.to_string())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ::errors::Error;
+
+            fn from_str(s: &str) -> ::errors::Result<$name> {
+                if s.is_empty() {
+                    bail!("Empty {}", stringify!($name));
+                }
+                Ok($name(String::from(s)))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl Deserialize for $name {
+            fn deserialize<D: Deserializer>(deserializer: D) -> Result<$name, D::Error> {
+                deserializer.deserialize(IdVisitor).map($name)
+            }
+        }
+    }
+}
+
+/// Visits an ID sent either as a string or as a number.
+struct IdVisitor;
+
+impl de::Visitor for IdVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an ID, as a string or a number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(String::from(v))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<String, E> {
+        Ok(v)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<String, E> {
+        Ok(v.to_string())
+    }
+}
+
+string_id! {
+    /// Account ID.
+    pub struct AccountID;
+}
+
+string_id! {
+    /// List ID.
+    pub struct ListID;
+}
+
+string_id! {
+    /// Media ID.
+    pub struct MediaID;
+}
+
+string_id! {
+    /// Notification ID.
+    pub struct NotificationID;
+}
+
+string_id! {
+    /// Poll ID.
+    pub struct PollID;
+}
+
+string_id! {
+    /// Report ID.
+    pub struct ReportID;
+}
+
+string_id! {
+    /// Status ID.
+    pub struct StatusID;
+}
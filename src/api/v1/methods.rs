@@ -1,11 +1,21 @@
 //! This module contains the Mastodon API Methods `/api/v1`.
 //!
 
+use api::multipart::Multipart;
 use api::oauth::OAuthApp;
-use hyper::{Body, Method, Request, Uri};
+use hyper::{Body, Headers, Method, Request, Uri};
 use errors::*;
+use serde_urlencoded;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use super::entities;
+pub use super::ids::{AccountID, ListID, MediaID, NotificationID, ReportID, StatusID};
+use super::streaming::StreamingTimeline;
+use url::Url;
+use version::Capability;
 
 pub enum APIEndpoint {
     // AccountsMethod: Fetch Account
@@ -22,57 +32,479 @@ pub enum APIEndpoint {
     MuteAccount(AccountID),
     UnmuteAccount(AccountID),
     GetAccountRelationships(RelationshipsQuery),
-    SearchAccounts(SearchAccountsQuery)
+    SearchAccounts(SearchAccountsQuery),
+    // StatusesMethod: Fetch Status
+    FetchStatus(StatusID),
+    GetStatusContext(StatusID),
+    GetStatusCard(StatusID),
+    RebloggedBy(StatusID),
+    FavouritedBy(StatusID),
+    PostStatus(NewStatus),
+    DeleteStatus(StatusID),
+    ReblogStatus(StatusID),
+    UnreblogStatus(StatusID),
+    FavouriteStatus(StatusID),
+    UnfavouriteStatus(StatusID),
+    // TimelinesMethod: Home Timeline
+    HomeTimeline(HomeTimelineQuery),
+    PublicTimeline(TimelineQuery),
+    TagTimeline(String, TimelineQuery),
+    ListTimeline(ListID, HomeTimelineQuery),
+    // NotificationsMethod: Fetch Notifications
+    FetchNotifications(NotificationsQuery),
+    GetNotification(NotificationID),
+    ClearNotifications,
+    DismissNotification(NotificationID),
+    // BlocksMethod: Fetch Blocks
+    FetchBlocks(PageQuery),
+    // FavouritesMethod: Fetch Favourites
+    FetchFavourites(PageQuery),
+    // FollowRequestsMethod: Fetch Follow Requests
+    FetchFollowRequests(PageQuery),
+    AuthorizeFollowRequest(AccountID),
+    RejectFollowRequest(AccountID),
+    // InstancesMethod: Get Instance
+    GetInstance,
+    GetInstanceV2,
+    // MutesMethod: Fetch Mutes
+    FetchMutes(PageQuery),
+    // ReportsMethod: Fetch Reports
+    FetchReports,
+    ReportUser(NewReport),
+    // SearchMethod: Search Content
+    SearchContent(SearchContentsQuery),
+    SearchContentV2(SearchContentsQuery),
+    // MediaMethod: Upload Media
+    UploadMedia(MediaUpload),
+    UploadMediaV2(MediaUpload),
+    GetMedia(MediaID),
+    // AppsMethod: Verify App Credentials
+    VerifyAppCredentials,
+    // Pagination: Fetch the page at a `Link` cursor
+    FetchPage(Url),
+    // StreamingMethod: Stream a timeline
+    Streaming(StreamingTimeline)
+}
+
+impl APIEndpoint {
+    /// Returns the `Capability` the instance needs to support the endpoint, if any.
+    pub fn capability(&self) -> Option<Capability> {
+        match *self {
+            APIEndpoint::SearchContentV2(_) => Some(Capability::SearchV2),
+            APIEndpoint::UploadMediaV2(_) => Some(Capability::MediaV2),
+            APIEndpoint::GetInstanceV2 => Some(Capability::InstanceV2),
+            _ => None
+        }
+    }
 }
 
 pub trait APIEndpointRequest {
     fn bearer_token_request(&self, method: Method, uri: Uri) -> Result<Request<Body>>;
     fn build_request(&self, endpoint: APIEndpoint) -> Result<Request<Body>>;
-    fn send(&self, endpoint: APIEndpoint, dst: Arc<Mutex<Vec<u8>>>) -> Result<()>;
+    /// Sends the request for `endpoint`, writing the response body into `dst`.
+    ///
+    /// Returns the response headers.
+    fn send(&self, endpoint: APIEndpoint, dst: Arc<Mutex<Vec<u8>>>) -> Result<Headers>;
 }
 
 /// updatable fields for the authenticated user.
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+///
+/// let form_data = UserFormData::new(Some(String::from("Jane")), None, None, None)
+///     .avatar(MediaUpload::from_path("avatar.png", "image/png")?)
+///     .bot(true)
+///     .field("Website", "https://example.com");
+///
+#[derive(Debug, PartialEq)]
 pub struct UserFormData {
     display_name: Option<String>,
     note: Option<String>,
-    avatar: Option<String>,
-    header: Option<String>
+    avatar: Option<MediaUpload>,
+    header: Option<MediaUpload>,
+    locked: Option<bool>,
+    bot: Option<bool>,
+    discoverable: Option<bool>,
+    fields_attributes: Vec<(String, String)>
 }
 
 impl UserFormData {
-    pub fn new(display_name: Option<String>, note: Option<String>, avatar: Option<String>, header: Option<String>) -> Self {
+    pub fn new(display_name: Option<String>, note: Option<String>, avatar: Option<MediaUpload>, header: Option<MediaUpload>) -> Self {
         UserFormData {
             display_name,
             note,
             avatar,
-            header
+            header,
+            locked: None,
+            bot: None,
+            discoverable: None,
+            fields_attributes: Vec::new()
+        }
+    }
+
+    /// Image to upload as the avatar.
+    pub fn avatar(mut self, avatar: MediaUpload) -> Self {
+        self.avatar = Some(avatar);
+        self
+    }
+
+    /// Image to upload as the header.
+    pub fn header(mut self, header: MediaUpload) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Whether follow requests must be manually approved.
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Whether the account is operated by a bot.
+    pub fn bot(mut self, bot: bool) -> Self {
+        self.bot = Some(bot);
+        self
+    }
+
+    /// Whether the account is featured in the profile directory.
+    pub fn discoverable(mut self, discoverable: bool) -> Self {
+        self.discoverable = Some(discoverable);
+        self
+    }
+
+    /// Adds a profile metadata field. Up to 4 fields are allowed.
+    pub fn field(mut self, name: &str, value: &str) -> Self {
+        self.fields_attributes.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Returns whether the avatar or header images are to be uploaded.
+    pub fn has_images(&self) -> bool {
+        self.avatar.is_some() || self.header.is_some()
+    }
+
+    fn text_params(&self) -> Result<Vec<(String, String)>> {
+        if self.fields_attributes.len() > 4 {
+            bail!("A profile can not have more than 4 metadata fields");
+        }
+        let mut params = Vec::new();
+        if let Some(ref display_name) = self.display_name {
+            params.push((String::from("display_name"), display_name.clone()));
+        }
+        if let Some(ref note) = self.note {
+            params.push((String::from("note"), note.clone()));
+        }
+        if let Some(locked) = self.locked {
+            params.push((String::from("locked"), format!("{}", locked)));
+        }
+        if let Some(bot) = self.bot {
+            params.push((String::from("bot"), format!("{}", bot)));
+        }
+        if let Some(discoverable) = self.discoverable {
+            params.push((String::from("discoverable"), format!("{}", discoverable)));
+        }
+        for (i, &(ref name, ref value)) in self.fields_attributes.iter().enumerate() {
+            params.push((format!("fields_attributes[{}][name]", i), name.clone()));
+            params.push((format!("fields_attributes[{}][value]", i), value.clone()));
         }
+        Ok(params)
+    }
+
+    /// Validates and encodes the text fields as `application/x-www-form-urlencoded`.
+    ///
+    /// Images can only be sent with `multipart`.
+    pub fn form_encode(&self) -> Result<String> {
+        if self.has_images() {
+            bail!("Images must be uploaded as multipart/form-data");
+        }
+        let params = self.text_params()?;
+        serde_urlencoded::to_string(params).chain_err(|| "Could not encode user form data")
+    }
+
+    /// Validates and encodes every field, images included, as `multipart/form-data`.
+    pub fn multipart(&self) -> Result<Multipart> {
+        let mut form = Multipart::new();
+        for (name, value) in self.text_params()? {
+            form.text(&name, &value);
+        }
+        for (name, image) in vec![("avatar", &self.avatar), ("header", &self.header)] {
+            if let Some(ref image) = *image {
+                if image.data.is_empty() {
+                    bail!("Can not upload an empty {}", name);
+                }
+                form.file(name, &image.filename, &image.mime_type, &image.data)?;
+            }
+        }
+        Ok(form)
     }
 }
 
-/// Account ID.
-#[derive(Debug, PartialEq)]
-pub struct AccountID {
-    pub id: usize
+/// Form data for posting a new status.
+///
+/// let status = NewStatus::new("My tooted toot!")
+///     .in_reply_to(StatusID::from(1234))
+///     .spoiler_text("toot")
+///     .visibility(Visibility::Unlisted);
+///
+#[derive(Debug, Default, PartialEq)]
+pub struct NewStatus {
+    status: String,
+    in_reply_to_id: Option<StatusID>,
+    media_ids: Vec<MediaID>,
+    sensitive: Option<bool>,
+    spoiler_text: Option<String>,
+    visibility: Option<entities::Visibility>,
+    language: Option<String>
 }
 
-/// Notification ID.
-#[derive(Debug, PartialEq)]
-pub struct NotificationID {
-    pub id: usize
+impl NewStatus {
+    pub fn new(status: &str) -> Self {
+        NewStatus {
+            status: String::from(status),
+            ..NewStatus::default()
+        }
+    }
+
+    /// Reply to the status with the given ID.
+    pub fn in_reply_to(mut self, status_id: StatusID) -> Self {
+        self.in_reply_to_id = Some(status_id);
+        self
+    }
+
+    /// Attach an uploaded media to the status (maximum 4).
+    pub fn media(mut self, media_id: MediaID) -> Self {
+        self.media_ids.push(media_id);
+        self
+    }
+
+    /// Mark the media of the status as NSFW.
+    pub fn sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = Some(sensitive);
+        self
+    }
+
+    /// Text to be shown as a warning before the actual content.
+    pub fn spoiler_text(mut self, spoiler_text: &str) -> Self {
+        self.spoiler_text = Some(String::from(spoiler_text));
+        self
+    }
+
+    /// Either `Direct`, `Private`, `Unlisted` or `Public`. Instances with other visibilities,
+    /// such as `local`, accept them through `Visibility::Other`, which is sent as is.
+    pub fn visibility(mut self, visibility: entities::Visibility) -> Self {
+        self.visibility = Some(visibility);
+        self
+    }
+
+    /// ISO 639-2 language code of the status.
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(String::from(language));
+        self
+    }
+
+    /// Checks that the status can be posted, before any request is sent.
+    pub fn validate(&self) -> Result<()> {
+        if self.status.trim().is_empty() && self.media_ids.is_empty() {
+            bail!("A status must have either text or media attached");
+        }
+        if self.media_ids.len() > 4 {
+            bail!("A status can not have more than 4 media attached");
+        }
+        Ok(())
+    }
+
+    /// Validates and encodes the status as `application/x-www-form-urlencoded`.
+    pub fn form_encode(&self) -> Result<String> {
+        self.validate()?;
+        let mut params = vec![("status", self.status.clone())];
+        if let Some(ref status) = self.in_reply_to_id {
+            params.push(("in_reply_to_id", status.to_string()));
+        }
+        for media in &self.media_ids {
+            params.push(("media_ids[]", media.to_string()));
+        }
+        if let Some(sensitive) = self.sensitive {
+            params.push(("sensitive", format!("{}", sensitive)));
+        }
+        if let Some(ref spoiler_text) = self.spoiler_text {
+            params.push(("spoiler_text", spoiler_text.clone()));
+        }
+        if let Some(ref visibility) = self.visibility {
+            params.push(("visibility", visibility.to_string()));
+        }
+        if let Some(ref language) = self.language {
+            params.push(("language", language.clone()));
+        }
+        serde_urlencoded::to_string(params).chain_err(|| "Could not encode status form data")
+    }
 }
 
-/// Status ID.
+/// Form data for reporting a user.
+///
+/// let report = NewReport::new(AccountID::from(1234))
+///     .status(StatusID::from(5678))
+///     .comment("Spamming the local timeline")
+///     .forward(true)
+///     .category("spam");
+///
 #[derive(Debug, PartialEq)]
-pub struct StatusID {
-    pub id: usize
+pub struct NewReport {
+    account_id: AccountID,
+    status_ids: Vec<StatusID>,
+    comment: Option<String>,
+    forward: Option<bool>,
+    category: Option<String>
 }
 
-/// Tag ID.
+impl NewReport {
+    pub fn new(account_id: AccountID) -> Self {
+        NewReport {
+            account_id,
+            status_ids: Vec::new(),
+            comment: None,
+            forward: None,
+            category: None
+        }
+    }
+
+    /// A status to attach to the report. Can be called multiple times.
+    pub fn status(mut self, status_id: StatusID) -> Self {
+        self.status_ids.push(status_id);
+        self
+    }
+
+    /// The reason for the report, up to 1000 characters.
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.comment = Some(String::from(comment));
+        self
+    }
+
+    /// Whether to forward the report to the remote instance, for remote accounts.
+    pub fn forward(mut self, forward: bool) -> Self {
+        self.forward = Some(forward);
+        self
+    }
+
+    /// Either "spam", "legal", "violation" or "other".
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(String::from(category));
+        self
+    }
+
+    /// Checks that the report can be filed, before any request is sent.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref comment) = self.comment {
+            if comment.chars().count() > 1000 {
+                bail!("A report comment can not be longer than 1000 characters");
+            }
+        }
+        if let Some(ref category) = self.category {
+            match category.as_str() {
+                "spam" | "legal" | "violation" | "other" => {},
+                _ => bail!("Invalid report category: {}", category)
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates and encodes the report as `application/x-www-form-urlencoded`.
+    pub fn form_encode(&self) -> Result<String> {
+        self.validate()?;
+        let mut params = vec![("account_id", self.account_id.to_string())];
+        for status in &self.status_ids {
+            params.push(("status_ids[]", status.to_string()));
+        }
+        if let Some(ref comment) = self.comment {
+            params.push(("comment", comment.clone()));
+        }
+        if let Some(forward) = self.forward {
+            params.push(("forward", format!("{}", forward)));
+        }
+        if let Some(ref category) = self.category {
+            params.push(("category", category.clone()));
+        }
+        serde_urlencoded::to_string(params).chain_err(|| "Could not encode report form data")
+    }
+}
+
+/// Interval between checks of whether an upload to `/api/v2/media` finished processing.
+pub const MEDIA_PROCESSING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of checks before giving up on an upload to `/api/v2/media`.
+pub const MEDIA_PROCESSING_ATTEMPTS: usize = 60;
+
+/// Form data for uploading a media attachment.
+///
+/// let media = MediaUpload::from_path("cat.png", "image/png")?
+///     .description("A cat, sitting on a keyboard")
+///     .focus(0.0, -0.5);
+///
 #[derive(Debug, PartialEq)]
-pub struct TagID {
-    pub id: usize
+pub struct MediaUpload {
+    filename: String,
+    mime_type: String,
+    data: Vec<u8>,
+    description: Option<String>,
+    focus: Option<(f32, f32)>
+}
+
+impl MediaUpload {
+    /// Uploads a buffer of bytes, with the given file name and MIME type.
+    pub fn from_bytes(filename: &str, mime_type: &str, data: Vec<u8>) -> Self {
+        MediaUpload {
+            filename: String::from(filename),
+            mime_type: String::from(mime_type),
+            data,
+            description: None,
+            focus: None
+        }
+    }
+
+    /// Uploads all the bytes of `reader`, with the given file name and MIME type.
+    pub fn from_reader<R: Read>(filename: &str, mime_type: &str, mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).chain_err(|| "Could not read media")?;
+        Ok(MediaUpload::from_bytes(filename, mime_type, data))
+    }
+
+    /// Uploads the file at `path`, with the given MIME type.
+    pub fn from_path<P: AsRef<Path>>(path: P, mime_type: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let filename = path.file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| Error::from(format!("Invalid media file name: {}", path.display())))?;
+        let file = File::open(path).chain_err(|| format!("Could not open media: {}", path.display()))?;
+        MediaUpload::from_reader(filename, mime_type, file)
+    }
+
+    /// Plain-text description of the media, for the visually impaired.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(String::from(description));
+        self
+    }
+
+    /// Focal point of the media, with `x` and `y` between `-1.0` and `1.0`.
+    pub fn focus(mut self, x: f32, y: f32) -> Self {
+        self.focus = Some((x, y));
+        self
+    }
+
+    /// Validates and encodes the media as `multipart/form-data`.
+    pub fn multipart(&self) -> Result<Multipart> {
+        if self.data.is_empty() {
+            bail!("Can not upload empty media");
+        }
+        let mut form = Multipart::new();
+        form.file("file", &self.filename, &self.mime_type, &self.data)?;
+        if let Some(ref description) = self.description {
+            form.text("description", description);
+        }
+        if let Some((x, y)) = self.focus {
+            if x < -1.0 || x > 1.0 || y < -1.0 || y > 1.0 {
+                bail!("Invalid media focus: ({}, {})", x, y);
+            }
+            form.text("focus", &format!("{},{}", x, y));
+        }
+        Ok(form)
+    }
 }
 
 /// Fields to query accounts.
@@ -87,27 +519,153 @@ pub struct SearchAccountsQuery {
     pub limit: Option<usize>
 }
 
+/// The types of results to search for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchType {
+    Accounts,
+    Hashtags,
+    Statuses
+}
+
+impl SearchType {
+    /// Returns the value of the `type` query parameter.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            SearchType::Accounts => "accounts",
+            SearchType::Hashtags => "hashtags",
+            SearchType::Statuses => "statuses"
+        }
+    }
+}
+
 /// Fields to query contents.
+#[derive(Debug, Default, PartialEq)]
 pub struct SearchContentsQuery {
     pub q: String,
-    pub resolve: Option<bool>
+    pub search_type: Option<SearchType>,
+    pub resolve: Option<bool>,
+    pub following: Option<bool>,
+    pub account_id: Option<AccountID>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>
 }
 
-/// Fields to query the home timeline.
-pub struct HomeTimelineQuery {
-    pub max_id: Option<usize>,
-    pub since_id: Option<usize>,
+impl SearchContentsQuery {
+    /// Returns the query pairs for the fields that are set.
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![("q", self.q.clone())];
+        if let Some(ref search_type) = self.search_type {
+            pairs.push(("type", String::from(search_type.as_str())));
+        }
+        if let Some(resolve) = self.resolve {
+            pairs.push(("resolve", format!("{}", resolve)));
+        }
+        if let Some(following) = self.following {
+            pairs.push(("following", format!("{}", following)));
+        }
+        if let Some(ref account) = self.account_id {
+            pairs.push(("account_id", account.to_string()));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(("offset", format!("{}", offset)));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", format!("{}", limit)));
+        }
+        pairs
+    }
+}
+
+/// Fields to query notifications.
+#[derive(Debug, Default, PartialEq)]
+pub struct NotificationsQuery {
+    pub max_id: Option<String>,
+    pub since_id: Option<String>,
+    pub limit: Option<usize>,
+    pub exclude_types: Vec<entities::NotificationType>,
+    pub account_id: Option<AccountID>
+}
+
+impl NotificationsQuery {
+    /// Returns the query pairs for the fields that are set.
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref max_id) = self.max_id {
+            pairs.push(("max_id", max_id.clone()));
+        }
+        if let Some(ref since_id) = self.since_id {
+            pairs.push(("since_id", since_id.clone()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", format!("{}", limit)));
+        }
+        for notification_type in &self.exclude_types {
+            pairs.push(("exclude_types[]", String::from(notification_type.as_str())));
+        }
+        if let Some(ref account) = self.account_id {
+            pairs.push(("account_id", account.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Fields to page through lists of accounts or statuses.
+#[derive(Debug, Default, PartialEq)]
+pub struct PageQuery {
+    pub max_id: Option<String>,
+    pub since_id: Option<String>,
     pub limit: Option<usize>
 }
 
+impl PageQuery {
+    /// Returns the query pairs for the fields that are set.
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(ref max_id) = self.max_id {
+            pairs.push(("max_id", max_id.clone()));
+        }
+        if let Some(ref since_id) = self.since_id {
+            pairs.push(("since_id", since_id.clone()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", format!("{}", limit)));
+        }
+        pairs
+    }
+}
+
+/// Fields to query the home/list timeline.
+pub type HomeTimelineQuery = PageQuery;
+
 /// Fields to query the public/tag timeline.
+#[derive(Debug, Default, PartialEq)]
 pub struct TimelineQuery {
     pub local: Option<bool>,
-    pub max_id: Option<usize>,
-    pub since_id: Option<usize>,
+    pub max_id: Option<String>,
+    pub since_id: Option<String>,
     pub limit: Option<usize>
 }
 
+impl TimelineQuery {
+    /// Returns the query pairs for the fields that are set.
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(local) = self.local {
+            pairs.push(("local", format!("{}", local)));
+        }
+        if let Some(ref max_id) = self.max_id {
+            pairs.push(("max_id", max_id.clone()));
+        }
+        if let Some(ref since_id) = self.since_id {
+            pairs.push(("since_id", since_id.clone()));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", format!("{}", limit)));
+        }
+        pairs
+    }
+}
+
 /// Methods for interacting with accounts on a Mastodon node.
 pub trait Accounts {
     /// Fetching an account:
@@ -134,7 +692,8 @@ pub trait Accounts {
     /// PATCH /api/v1/accounts/update_credentials
     /// ```
     ///
-    /// `UserFormData` form data is required.
+    /// `UserFormData` form data is required. It is sent as `multipart/form-data` when an avatar
+    /// or header image is uploaded, or else as `application/x-www-form-urlencoded`.
     fn update_current_user(&self, form_data: UserFormData) -> Result<entities::Account>;
 
     /// Getting an account's followers:
@@ -297,7 +856,17 @@ pub trait Apps {
     /// `website` URL to the homepage of your app. It is optional.
     ///
     /// Creates and returns a new `OAuthApp`.
-    fn register_app(&self, name: &str, uris: &str, scopes: &str, website: &str) -> OAuthApp;
+    fn register_app(&self, name: &str, uris: &str, scopes: &str, website: &str) -> Result<OAuthApp>;
+
+    /// Verifying the credentials of an application:
+    ///
+    /// ```norun
+    /// GET /api/v1/apps/verify_credentials
+    /// ```
+    ///
+    /// Returns the `Application` the Bearer token was issued to, confirming that its registration
+    /// is still valid.
+    fn verify_app_credentials(&self) -> Result<entities::Application>;
 }
 
 pub trait Blocks {
@@ -315,7 +884,7 @@ pub trait Blocks {
     /// `limit` Maximum number of blocks to get (Default 40, Max 80). It is optional.
     ///
     /// Returns an array of `Account`s blocked by the authenticated user.
-    fn fetch_blocks(&self, query: String) -> Result<Vec<entities::Account>>;
+    fn fetch_blocks(&self, query: PageQuery) -> Result<Vec<entities::Account>>;
 }
 pub trait Favourites {
     /// Fetching a user's favourites:
@@ -331,8 +900,8 @@ pub trait Favourites {
     ///
     /// `limit` Maximum number of favourites to get (Default 40, Max 80). It is optional.
     ///
-    /// Returns an array of `Account`s favourited by the authenticated user.
-    fn fetch_favourites(&self, query: String) -> Result<Vec<entities::Account>>;
+    /// Returns an array of `Status`es favourited by the authenticated user.
+    fn fetch_favourites(&self, query: PageQuery) -> Result<Vec<entities::Status>>;
 }
 
 pub trait FollowRequests {
@@ -350,7 +919,9 @@ pub trait FollowRequests {
     /// `limit` Maximum number of follow requests to get (Default 40, Max 80). It is optional.
     ///
     /// Returns an array of `Account`s which have requested to follow the authenticated user.
-    fn fetch_follow_requests(&self, query: String) -> Result<Vec<entities::Account>>;
+    ///
+    /// The results are paginated with the `Link` header, see `Client::iter_follow_requests`.
+    fn fetch_follow_requests(&self, query: PageQuery) -> Result<Vec<entities::Account>>;
 
     /// Authorizing follow requests
     ///
@@ -362,8 +933,8 @@ pub trait FollowRequests {
     ///
     /// `id` The id of the account to authorize. It is required.
     ///
-    /// Returns an empty object.
-    fn authorize_follow_request(&self, form_data: String) -> Result<()>;
+    /// Returns the updated `Relationship` with the account.
+    fn authorize_follow_request(&self, account_id: AccountID) -> Result<entities::Relationship>;
 
     /// Rejecting follow requests
     ///
@@ -373,25 +944,30 @@ pub trait FollowRequests {
     ///
     /// Parameters:
     ///
-    /// `id` The id of the account to authorize. It is required.
+    /// `id` The id of the account to reject. It is required.
     ///
-    /// Returns an empty object.
-    fn reject_follow_request(&self, form_data: String) -> Result<()>;
+    /// Returns the updated `Relationship` with the account.
+    fn reject_follow_request(&self, account_id: AccountID) -> Result<entities::Relationship>;
 }
 
 pub trait Follows {
     /// Following a remote user:
     ///
     /// ```norun
-    /// POST /api/v1/follows
+    /// GET /api/v2/search?q=:acct&resolve=true&type=accounts
+    /// POST /api/v1/accounts/:account_id/follow
     /// ```
     ///
-    /// Form data:
+    /// Parameters:
+    ///
+    /// `acct` `username@domain` of the person you want to follow. It is required.
     ///
-    /// `uri` `username@domain` of the person you want to follow. It is required.
+    /// The account is resolved to its local `AccountID` through search. When the handle is not
+    /// found, it is looked up with WebFinger on the remote host, and its canonical handle is
+    /// resolved instead.
     ///
-    /// Returns the local representation of the followed `Account`.
-    fn follow_remote_user(&self, form_data: String) -> Result<entities::Account>;
+    /// Returns the `Relationship` with the followed account.
+    fn follow_remote_user(&self, acct: &str) -> Result<entities::Relationship>;
 }
 
 pub trait Instances {
@@ -405,6 +981,17 @@ pub trait Instances {
     ///
     /// Does not require authentication.
     fn get_instance(&self) -> Result<entities::Instance>;
+
+    /// Getting instance information, in its newer format:
+    ///
+    /// ```norun
+    /// GET /api/v2/instance
+    /// ```
+    ///
+    /// Returns the current `InstanceV2`.
+    ///
+    /// Does not require authentication.
+    fn get_instance_v2(&self) -> Result<entities::InstanceV2>;
 }
 
 pub trait Media {
@@ -416,10 +1003,36 @@ pub trait Media {
     ///
     /// Form data:
     ///
-    /// `file` Media to be uploaded. It is required.
+    /// `file` Media to be uploaded, as `multipart/form-data`. It is required.
+    ///
+    /// `description` Plain-text description of the media, for the visually impaired. It is
+    /// optional.
+    ///
+    /// `focus` Two floating points, between `-1.0` and `1.0`, for the focal point. It is optional.
     ///
     /// Returns an `Attachment`that can be used when creating a status.
-    fn upload_media(&self, form_data: String) -> Result<entities::Attachment>;
+    fn upload_media(&self, media: MediaUpload) -> Result<entities::Attachment>;
+
+    /// Uploading a media attachment, with asynchronous processing:
+    ///
+    /// ```norun
+    /// POST /api/v2/media
+    /// ```
+    ///
+    /// Takes the same form data as `upload_media`. Large media is processed by the server after
+    /// the upload, so `GET /api/v1/media/:media_id` is polled until the `Attachment` has a `url`.
+    ///
+    /// Returns the processed `Attachment`.
+    fn upload_media_v2(&self, media: MediaUpload) -> Result<entities::Attachment>;
+
+    /// Getting a media attachment:
+    ///
+    /// ```norun
+    /// GET /api/v1/media/:media_id
+    /// ```
+    ///
+    /// Returns the `Attachment`, whose `url` is not set while it is still being processed.
+    fn get_media(&self, media_id: MediaID) -> Result<entities::Attachment>;
 }
 
 pub trait Mutes {
@@ -438,7 +1051,7 @@ pub trait Mutes {
     /// `limit` Maximum number of mutes to get (Default 40, Max 80). It is optional.
     ///
     /// Returns an array of `Account`s muted by the authenticated user.
-    fn fetch_mutes(&self, query: String) -> Result<Vec<entities::Account>>;
+    fn fetch_mutes(&self, query: PageQuery) -> Result<Vec<entities::Account>>;
 }
 
 pub trait Notifications {
@@ -454,10 +1067,14 @@ pub trait Notifications {
     ///
     /// `since_id` Get a list of notifications with ID greater than this value. It is optional.
     ///
-    /// `limit` Maximum number of notifications to get (Default 40, Max 80). It is optional.
+    /// `limit` Maximum number of notifications to get (Default 15, Max 30). It is optional.
+    ///
+    /// `exclude_types` Array of notification types to exclude. It is optional.
+    ///
+    /// `account_id` Only return notifications received from this account. It is optional.
     ///
     /// Returns an array of `Notification`s for the authenticated user.
-    fn fetch_notifications(&self, query: String) -> Result<Vec<entities::Notification>>;
+    fn fetch_notifications(&self, query: NotificationsQuery) -> Result<Vec<entities::Notification>>;
 
     /// Getting a single notification:
     ///
@@ -477,6 +1094,20 @@ pub trait Notifications {
     /// Deletes all notifications from the Mastodon server for the authenticated user. Returns an
     /// empty object.
     fn clear_notifications(&self) -> Result<()>;
+
+    /// Dismissing a single notification:
+    ///
+    /// ```norun
+    /// POST /api/v1/notifications/dismiss
+    /// ```
+    ///
+    /// Form data:
+    ///
+    /// `id` The ID of the notification to dismiss. It is required.
+    ///
+    /// Deletes the notification from the Mastodon server for the authenticated user. Returns an
+    /// empty object.
+    fn dismiss_notification(&self, notification_id: NotificationID) -> Result<()>;
 }
 
 pub trait Reports {
@@ -499,12 +1130,16 @@ pub trait Reports {
     ///
     /// `account_id` The ID of the account to report. It is required.
     ///
-    /// `status_ids` The IDs of the status to report (can be an array). It is required.
+    /// `status_ids[]` The IDs of the statuses to report (can be an array). It is optional.
+    ///
+    /// `comment` A comment to associate with the report. It is optional.
+    ///
+    /// `forward` Whether to forward the report to the remote instance. It is optional.
     ///
-    /// `comment` A comment to associate with the report. It is required.
+    /// `category` Either "spam", "legal", "violation" or "other". It is optional.
     ///
     /// Returns the finished `Report`.
-    fn reporting_user(&self, form_data: String) -> Result<entities::Report>;
+    fn reporting_user(&self, report: NewReport) -> Result<entities::Report>;
 }
 
 pub trait Search {
@@ -514,19 +1149,40 @@ pub trait Search {
     /// GET /api/v1/search
     /// ```
     ///
-    /// Form data:
+    /// Query parameters:
     ///
     /// `q` The search query. It is required.
     ///
-    /// `resolve` Whether to resolve non-local accounts. It is required.
+    /// `type` Either "accounts", "hashtags" or "statuses". It is optional.
+    ///
+    /// `resolve` Whether to resolve non-local accounts and statuses. It is optional.
+    ///
+    /// `following` Whether to only include accounts that the user is following. It is optional.
+    ///
+    /// `account_id` Only include statuses posted by this account. It is optional.
+    ///
+    /// `offset` Number of results to skip. It is optional.
+    ///
+    /// `limit` Maximum number of results per type. It is optional.
     ///
-    /// Returns `Results`.
+    /// Returns `Results`, with hashtags as plain names.
     ///
     /// If `q` is a URL, Mastodon will attempt to fetch the provided account or status. Otherwise,
     /// it will do a local account and hashtag search.
     ///
     /// Does not require authentication.
     fn search_content(&self, query: SearchContentsQuery) -> Result<entities::Results>;
+
+    /// Searching for content, with typed results:
+    ///
+    /// ```norun
+    /// GET /api/v2/search
+    /// ```
+    ///
+    /// Takes the same query parameters as `search_content`.
+    ///
+    /// Returns `ResultsV2`, with hashtags as `Tag`s.
+    fn search_content_v2(&self, query: SearchContentsQuery) -> Result<entities::ResultsV2>;
 }
 
 pub trait Statuses {
@@ -550,7 +1206,7 @@ pub trait Statuses {
     /// Returns a `Context`.
     ///
     /// Does not require authentication.
-    fn get_status_context(&self, status_id: StatusID) -> Result<entities::Status>;
+    fn get_status_context(&self, status_id: StatusID) -> Result<entities::Context>;
 
     /// Getting a card associated with a status:
     ///
@@ -610,14 +1266,25 @@ pub trait Statuses {
     ///
     /// Form data:
     ///
-    /// `status`  The text of the status  no
+    /// `status`  The text of the status. It is required, unless `media_ids` is given.
+    ///
     /// `in_reply_to_id`  local ID of the status you want to reply to. It is optional.
-    /// `media_ids`   Array of media IDs to attach to the status (maximum 4). It is optional.    /// `sensitive`   Set this to mark the media of the status as NSFW. It is optional.
+    ///
+    /// `media_ids`   Array of media IDs to attach to the status (maximum 4). It is optional.
+    ///
+    /// `sensitive`   Set this to mark the media of the status as NSFW. It is optional.
+    ///
     /// `spoiler_text`    Text to be shown as a warning before the actual content. It is optional.
-    /// `visibility`  Either "direct", "private", "unlisted" or "public". It is optional.
+    ///
+    /// `visibility`  Either `Direct`, `Private`, `Unlisted`, `Public`, or an instance-specific
+    /// `Other` value. It is optional.
+    ///
+    /// `language`  ISO 639-2 language code of the status. It is optional.
+    ///
+    /// `NewStatus` form data is required, and is validated before the request is sent.
     ///
     /// Returns the new `Status`.
-    fn post_status(&self, form_data: String) -> Result<entities::Status>;
+    fn post_status(&self, new_status: NewStatus) -> Result<entities::Status>;
 
     /// Deleting a status:
     ///
@@ -706,12 +1373,14 @@ pub trait Timelines {
     /// Does not require authentication.
     fn public_timeline(&self, query: TimelineQuery) -> Result<Vec<entities::Status>>;
 
-    /// Retrieving a timeline:
+    /// Retrieving a hashtag timeline:
     ///
     /// ```norun
     /// GET /api/v1/timelines/tag/:hashtag
     /// ```
     ///
+    /// `hashtag` is the name of the tag, with or without the leading `#`.
+    ///
     /// Query parameters:
     ///
     /// `local`   Only return statuses originating from this instance. It is optional.
@@ -726,5 +1395,23 @@ pub trait Timelines {
     /// Returns an array of `Status`es, most recent ones first.
     ///
     /// Does not require authentication.
-    fn tag_timeline(&self, hashtag: TagID, query: TimelineQuery) -> Result<Vec<entities::Status>>;
+    fn tag_timeline(&self, hashtag: &str, query: TimelineQuery) -> Result<Vec<entities::Status>>;
+
+    /// Retrieving a list timeline:
+    ///
+    /// ```norun
+    /// GET /api/v1/timelines/list/:list_id
+    /// ```
+    ///
+    /// Query parameters:
+    ///
+    /// `max_id`  Get a list of timelines with ID less than or equal this value. It is optional.
+    ///
+    /// `since_id`    Get a list of timelines with ID greater than this value. It is optional.
+    ///
+    /// `limit`   Maximum number of statuses on the requested timeline to get (Default 20, Max 40).
+    /// It is optional.
+    ///
+    /// Returns an array of `Status`es from the accounts in the list, most recent ones first.
+    fn list_timeline(&self, list_id: ListID, query: HomeTimelineQuery) -> Result<Vec<entities::Status>>;
 }
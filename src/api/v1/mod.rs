@@ -1,4 +1,9 @@
 //! This module contains the Mastodon API Methods and Entities `/api/v1`.
+pub mod async_client;
 pub mod client;
+pub mod decoding;
 pub mod entities;
+pub mod ids;
 pub mod methods;
+pub mod pagination;
+pub mod streaming;
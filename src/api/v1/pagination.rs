@@ -0,0 +1,107 @@
+//! This module contains the pagination of list endpoints, via the HTTP `Link` header.
+//!
+use errors::*;
+use hyper::header::{Headers, Link, RelationType};
+use serde::Deserialize;
+use std::vec;
+use super::client::Client;
+use super::methods::APIEndpoint;
+use url::Url;
+
+/// A page of results from a paginated endpoint.
+///
+/// `next` and `prev` are the cursors sent by the server in the `Link` header, pointing to
+/// the following (older) and preceding (newer) pages of results.
+#[derive(Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Url>,
+    pub prev: Option<Url>
+}
+
+impl<T> Page<T> {
+    /// Creates a new `Page`, taking its cursors from the `Link` header, if present.
+    pub fn new(items: Vec<T>, headers: &Headers) -> Self {
+        let mut page = Page { items, next: None, prev: None };
+        if let Some(link) = headers.get::<Link>() {
+            for value in link.values() {
+                let url = match Url::parse(value.link()) {
+                    Ok(url) => url,
+                    Err(_) => continue
+                };
+                let rel = value.rel().unwrap_or(&[]);
+                if rel.contains(&RelationType::Next) {
+                    page.next = Some(url);
+                } else if rel.contains(&RelationType::Prev) {
+                    page.prev = Some(url);
+                }
+            }
+        }
+        page
+    }
+}
+
+/// A lazy iterator over every item of a paginated endpoint.
+///
+/// Pages are only requested when the items of the current one run out, following the `next`
+/// cursor until there are no more pages, or until the optional item cap is reached.
+pub struct Pages<'a, T> {
+    client: &'a Client,
+    fetch: fn(&Client, APIEndpoint) -> Result<Page<T>>,
+    next: Option<APIEndpoint>,
+    items: vec::IntoIter<T>,
+    remaining: Option<usize>
+}
+
+impl<'a, T: Deserialize> Pages<'a, T> {
+    pub fn new(client: &'a Client, endpoint: APIEndpoint, cap: Option<usize>) -> Self {
+        Pages::with_fetch(client, endpoint, cap, Client::fetch_page)
+    }
+}
+
+impl<'a, T> Pages<'a, T> {
+    /// Creates a new `Pages`, requesting and decoding each page with `fetch`.
+    pub fn with_fetch(client: &'a Client,
+                      endpoint: APIEndpoint,
+                      cap: Option<usize>,
+                      fetch: fn(&Client, APIEndpoint) -> Result<Page<T>>) -> Self {
+        Pages {
+            client,
+            fetch,
+            next: Some(endpoint),
+            items: Vec::new().into_iter(),
+            remaining: cap
+        }
+    }
+}
+
+impl<'a, T> Iterator for Pages<'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        loop {
+            if let Some(item) = self.items.next() {
+                if let Some(ref mut remaining) = self.remaining {
+                    *remaining -= 1;
+                }
+                return Some(Ok(item));
+            }
+            let endpoint = match self.next.take() {
+                Some(endpoint) => endpoint,
+                None => return None
+            };
+            match (self.fetch)(self.client, endpoint) {
+                Ok(page) => {
+                    if !page.items.is_empty() {
+                        self.next = page.next.map(APIEndpoint::FetchPage);
+                    }
+                    self.items = page.items.into_iter();
+                },
+                Err(e) => return Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! This module contains the Streaming API `/api/v1/streaming`, which pushes server-sent events.
+//!
+use errors::*;
+use futures::{Async, Future, Poll, Stream};
+use hyper::{Body, Uri};
+use hyper::client::FutureResponse as HttpFutureResponse;
+use hyper::header::{Authorization, Bearer, Headers, Location};
+use reactor::HttpsClient;
+use std::cmp;
+use std::collections::VecDeque;
+use std::mem;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use super::client::Client;
+use super::decoding::Decoding;
+use super::entities;
+use super::methods::{APIEndpoint, APIEndpointRequest, ListID, StatusID};
+use tokio_core::reactor::{Handle, Timeout};
+use url::Url;
+
+/// Time without receiving any data, not even a heartbeat, before the connection is considered
+/// stale and is re-opened. Mastodon sends a heartbeat every 15 seconds.
+pub const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Delay before the first attempt to reconnect, doubled on every failed attempt.
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Maximum delay between attempts to reconnect.
+pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Maximum number of redirects followed when connecting to the Streaming API.
+pub const MAX_REDIRECTS: usize = 5;
+
+/// Returns the `http(s)` base URL of a Streaming API announced as `ws(s)`, as in the
+/// `urls.streaming_api` of an `Instance`.
+pub fn streaming_base_url(streaming_api: &str) -> Result<Url> {
+    let streaming_api = if streaming_api.starts_with("wss://") {
+        format!("https://{}", &streaming_api[6..])
+    } else if streaming_api.starts_with("ws://") {
+        format!("http://{}", &streaming_api[5..])
+    } else {
+        String::from(streaming_api)
+    };
+    Url::parse(&streaming_api).chain_err(|| format!("Invalid streaming URL: {}", streaming_api))
+}
+
+/// The timelines that can be streamed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StreamingTimeline {
+    /// Events for the authenticated user, `/api/v1/streaming/user`.
+    User,
+    /// All public statuses, `/api/v1/streaming/public`.
+    Public,
+    /// All public statuses local to the instance, `/api/v1/streaming/public/local`.
+    PublicLocal,
+    /// All public statuses for a hashtag, `/api/v1/streaming/hashtag?tag=:hashtag`.
+    Hashtag(String),
+    /// All statuses from the accounts in a list, `/api/v1/streaming/list?list=:list_id`.
+    List(ListID)
+}
+
+/// An event pushed by the Streaming API.
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    /// A new `Status` has appeared.
+    Update(entities::Status),
+    /// A new `Notification` has appeared.
+    Notification(entities::Notification),
+    /// The status with the given ID has been deleted.
+    Delete(StatusID),
+    /// The keyword filters of the user have been changed.
+    FiltersChanged
+}
+
+impl Event {
+    /// Decodes the `data` of a server-sent event named `name`, with the given `decoding`.
+    ///
+    /// Returns `None` for events unknown to herder.
+    pub fn from_sse(name: &str, data: &str, decoding: Decoding) -> Result<Option<Event>> {
+        let event = match name {
+            "update" => {
+                let status = decoding.decode(data.as_bytes()).chain_err(|| "Unexpected JSON error decoding update.")?;
+                Event::Update(status)
+            },
+            "notification" => {
+                let notification = decoding.decode(data.as_bytes())
+                    .chain_err(|| "Unexpected JSON error decoding notification.")?;
+                Event::Notification(notification)
+            },
+            "delete" => {
+                let id = StatusID::from_str(data.trim()).chain_err(|| "Invalid ID of deleted status.")?;
+                Event::Delete(id)
+            },
+            "filters_changed" => Event::FiltersChanged,
+            _ => return Ok(None)
+        };
+        Ok(Some(event))
+    }
+}
+
+/// An incremental parser of `text/event-stream` data.
+///
+/// The default parser decodes the events leniently.
+#[derive(Debug, Default)]
+pub struct EventParser {
+    decoding: Decoding,
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>
+}
+
+impl EventParser {
+    /// Creates a parser decoding the events with the given `decoding`.
+    pub fn new(decoding: Decoding) -> EventParser {
+        EventParser { decoding, ..EventParser::default() }
+    }
+
+    /// Feeds a chunk of the stream, returning the events it completes.
+    ///
+    /// Comments, such as the heartbeats sent by Mastodon, are skipped.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<Event>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..end + 1).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let line = String::from_utf8_lossy(&line);
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    events.push(event);
+                }
+            } else if line.starts_with(':') {
+                continue;
+            } else {
+                let (field, value) = match line.find(':') {
+                    Some(i) => (&line[..i], &line[i + 1..]),
+                    None => (&line[..], "")
+                };
+                let value = if value.starts_with(' ') { &value[1..] } else { value };
+                match field {
+                    "event" => self.event = Some(String::from(value)),
+                    "data" => self.data.push(String::from(value)),
+                    _ => {}
+                }
+            }
+        }
+        events
+    }
+
+    fn dispatch(&mut self) -> Option<Result<Event>> {
+        let name = self.event.take();
+        let data = mem::replace(&mut self.data, Vec::new());
+        if name.is_none() && data.is_empty() {
+            return None;
+        }
+        let name = name.unwrap_or_else(|| String::from("message"));
+        match Event::from_sse(&name, &data.join("\n"), self.decoding) {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e))
+        }
+    }
+}
+
+enum State {
+    Idle,
+    Connecting(HttpFutureResponse),
+    Streaming(Body),
+    Waiting(Timeout),
+    Done
+}
+
+/// A `Stream` of the `Event`s pushed for a `StreamingTimeline`.
+///
+/// Whenever the connection is closed, fails, or goes stale without heartbeats, it is re-opened
+/// after a growing delay. Each item is the `Result` of decoding an event, so that an event that
+/// can not be decoded is yielded as an `Err` item, without ending the stream. Redirects, e.g. to
+/// the streaming host of the instance, are followed and kept for the following connections,
+/// without sending the access token to another origin than that of the request: set the
+/// `streaming_url` of the `Client` to authenticate on another streaming host. The stream only
+/// fails when the server rejects the request, e.g. with a 401.
+pub struct EventStream {
+    api: Client,
+    http: HttpsClient,
+    handle: Handle,
+    timeline: StreamingTimeline,
+    state: State,
+    parser: EventParser,
+    events: VecDeque<Result<Event>>,
+    heartbeat: Timeout,
+    delay: Duration,
+    url: Option<Url>,
+    location: Option<Url>,
+    redirects: usize,
+    rejection: Option<Error>
+}
+
+impl EventStream {
+    pub fn new(api: Client,
+               http: HttpsClient,
+               handle: &Handle,
+               timeline: StreamingTimeline) -> Result<EventStream> {
+        Ok(EventStream {
+            parser: EventParser::new(api.decoding),
+            api,
+            http,
+            handle: handle.clone(),
+            timeline,
+            state: State::Idle,
+            events: VecDeque::new(),
+            heartbeat: Timeout::new(HEARTBEAT_TIMEOUT, handle)?,
+            delay: RECONNECT_DELAY,
+            url: None,
+            location: None,
+            redirects: 0,
+            rejection: None
+        })
+    }
+}
+
+/// Resolves the `Location` of a redirect against the `url` that was requested.
+fn redirect_location(url: &Option<Url>, headers: &Headers) -> Option<Url> {
+    let location = match headers.get::<Location>() {
+        Some(location) => location,
+        None => return None
+    };
+    match *url {
+        Some(ref url) => url.join(location).ok(),
+        None => Url::parse(location).ok()
+    }
+}
+
+/// Waits for `delay` before reconnecting, doubling it for the next attempt.
+fn reconnect(delay: &mut Duration, handle: &Handle, parser: &mut EventParser) -> Result<State> {
+    let timeout = Timeout::new(*delay, handle)?;
+    *delay = cmp::min(*delay * 2, MAX_RECONNECT_DELAY);
+    *parser = EventParser::new(parser.decoding);
+    Ok(State::Waiting(timeout))
+}
+
+impl Stream for EventStream {
+    type Item = Result<Event>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Result<Event>>, Error> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(Async::Ready(Some(event)));
+            }
+            let stale = match self.state {
+                State::Connecting(_) | State::Streaming(_) => match self.heartbeat.poll() {
+                    Ok(Async::NotReady) => false,
+                    _ => true
+                },
+                _ => false
+            };
+            if stale {
+                self.state = reconnect(&mut self.delay, &self.handle, &mut self.parser)?;
+                continue;
+            }
+            let next = match self.state {
+                State::Idle => {
+                    let mut req = self.api.build_request(APIEndpoint::Streaming(self.timeline.clone()))?;
+                    if let Some(ref location) = self.location {
+                        let requested = Url::parse(&req.uri().to_string()).ok();
+                        if requested.map(|url| url.origin()) != Some(location.origin()) {
+                            req.headers_mut().remove::<Authorization<Bearer>>();
+                        }
+                        req.set_uri(Uri::from_str(location.as_str()).chain_err(|| "Invalid redirect URI")?);
+                    }
+                    self.url = Url::parse(&req.uri().to_string()).ok();
+                    self.heartbeat.reset(Instant::now() + HEARTBEAT_TIMEOUT);
+                    State::Connecting(self.http.request(req))
+                },
+                State::Connecting(ref mut response) => match response.poll() {
+                    Ok(Async::Ready(res)) => {
+                        let status = res.status();
+                        let location = if status.is_redirection() && self.redirects < MAX_REDIRECTS {
+                            redirect_location(&self.url, res.headers())
+                        } else {
+                            None
+                        };
+                        if status.is_success() {
+                            self.delay = RECONNECT_DELAY;
+                            self.heartbeat.reset(Instant::now() + HEARTBEAT_TIMEOUT);
+                            self.redirects = 0;
+                            State::Streaming(res.body())
+                        } else if let Some(location) = location {
+                            self.location = Some(location);
+                            self.redirects += 1;
+                            State::Idle
+                        } else if status.is_server_error() {
+                            reconnect(&mut self.delay, &self.handle, &mut self.parser)?
+                        } else {
+                            self.rejection = Some(Error::from_response(status, res.headers(), &[]));
+                            State::Done
+                        }
+                    },
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Err(_) => reconnect(&mut self.delay, &self.handle, &mut self.parser)?
+                },
+                State::Streaming(ref mut body) => match body.poll() {
+                    Ok(Async::Ready(Some(chunk))) => {
+                        self.heartbeat.reset(Instant::now() + HEARTBEAT_TIMEOUT);
+                        self.events.extend(self.parser.feed(&chunk));
+                        continue;
+                    },
+                    Ok(Async::Ready(None)) | Err(_) => {
+                        reconnect(&mut self.delay, &self.handle, &mut self.parser)?
+                    },
+                    Ok(Async::NotReady) => return Ok(Async::NotReady)
+                },
+                State::Waiting(ref mut timeout) => match timeout.poll() {
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    _ => State::Idle
+                },
+                State::Done => return match self.rejection.take() {
+                    Some(rejection) => Err(rejection),
+                    None => Ok(Async::Ready(None))
+                }
+            };
+            self.state = next;
+        }
+    }
+}
@@ -1,10 +1,18 @@
 //! Create the Error, ErrorKind, ResultExt, and Result types.
+use api::v1::entities;
 use hyper;
+use hyper::StatusCode;
+use hyper::header::{Headers, HttpDate};
 use serde_json;
 use serde_urlencoded;
 use std::io;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 use url;
 
+/// Maximum number of characters of a non-JSON response body kept in an error.
+const SNIPPET_LENGTH: usize = 200;
+
 error_chain! {
     foreign_links {
         Io(io::Error);
@@ -14,4 +22,82 @@ error_chain! {
         Url(url::ParseError);
         Hyper(hyper::error::Error);
     }
+
+    errors {
+        Api(status: u16, error: String, description: Option<String>) {
+            description("the Mastodon API returned an error")
+            display("API error ({}): {}", status, error)
+        }
+        Unauthorized(error: String, description: Option<String>) {
+            description("the request is not authorized")
+            display("Unauthorized: {}", error)
+        }
+        NotFound(error: String, description: Option<String>) {
+            description("the requested resource was not found")
+            display("Not found: {}", error)
+        }
+        Validation(error: String, description: Option<String>) {
+            description("the request failed validation")
+            display("Validation failed: {}", error)
+        }
+        RateLimited(error: String, description: Option<String>, retry_after: Option<Duration>) {
+            description("the rate limit was exceeded")
+            display("Rate limited: {}", error)
+        }
+        Unsupported(capability: String, version: String) {
+            description("the method is unsupported by server")
+            display("Unsupported by server: {} requires a newer version than {}", capability, version)
+        }
+    }
+}
+
+impl Error {
+    /// Maps a non-success response, decoding its `entities::Error` body when possible.
+    ///
+    /// Other bodies, such as the HTML error pages of proxies, are only kept as a short snippet in
+    /// the description. Rate limited responses carry the delay of their `Retry-After` header, if
+    /// any.
+    pub fn from_response(status: StatusCode, headers: &Headers, body: &[u8]) -> Error {
+        let (error, description) = match serde_json::from_slice::<entities::Error>(body) {
+            Ok(entities::Error { error, error_description }) => (error, error_description),
+            Err(_) => (String::from(status.canonical_reason().unwrap_or("Unknown error")), snippet(body))
+        };
+        match status {
+            StatusCode::Unauthorized => ErrorKind::Unauthorized(error, description).into(),
+            StatusCode::NotFound => ErrorKind::NotFound(error, description).into(),
+            StatusCode::UnprocessableEntity => ErrorKind::Validation(error, description).into(),
+            StatusCode::TooManyRequests => {
+                ErrorKind::RateLimited(error, description, retry_after(headers)).into()
+            },
+            _ => ErrorKind::Api(u16::from(status), error, description).into()
+        }
+    }
+}
+
+/// Returns the beginning of a non-JSON response `body`, if it is not blank.
+fn snippet(body: &[u8]) -> Option<String> {
+    let body = String::from_utf8_lossy(body);
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    let mut snippet: String = body.chars().take(SNIPPET_LENGTH).collect();
+    if snippet.len() < body.len() {
+        snippet.push_str("...");
+    }
+    Some(snippet)
+}
+
+/// Returns the delay of the `Retry-After` header, given either in seconds or as an HTTP date.
+fn retry_after(headers: &Headers) -> Option<Duration> {
+    let value = match headers.get_raw("Retry-After").and_then(|raw| raw.one()) {
+        Some(value) => String::from_utf8_lossy(value).trim().to_owned(),
+        None => return None
+    };
+    if let Ok(seconds) = u64::from_str(&value) {
+        return Some(Duration::from_secs(seconds));
+    }
+    HttpDate::from_str(&value).ok().map(|date| {
+        SystemTime::from(date).duration_since(SystemTime::now()).unwrap_or_else(|_| Duration::from_secs(0))
+    })
 }
@@ -2,10 +2,12 @@
 //! compatible microblogging service.
 //!
 #![recursion_limit = "1024"]
+extern crate chrono;
 extern crate curl;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
+extern crate serde;
 extern crate serde_json;
 extern crate serde_urlencoded;
 extern crate tokio_core;
@@ -20,6 +22,9 @@ extern crate error_chain;
 pub mod api;
 pub mod mastodon;
 pub mod errors;
+pub mod reactor;
+pub mod version;
+pub mod webfinger;
 
-pub use api::Client;
+pub use api::{AsyncClient, Client};
 pub use mastodon::Mastodon;
@@ -1,19 +1,33 @@
 //! This module contains the code representing Mastodon nodes and API Clients
 //!
-use Client;
-use api::oauth::{CreateApp, OAuthApp};
+use {AsyncClient, Client};
+use api::oauth::{AccessToken, AuthorizationType, CreateApp, OAuthApp};
+use api::v1::methods::Instances;
 use errors::*;
 use hyper::header::Bearer;
+use reactor::Reactor;
 use serde_json;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
+use tokio_core::reactor::Handle;
 use url::Url;
+use version::Version;
 
 /// `Mastodon` is used to specify the base url of a Mastodon node.
 /// Only HTTPS connections are supported.
+///
+/// The blocking requests to the node, and those of its `Client`s, run on a `Reactor` shared by
+/// the current thread.
 #[derive(Debug)]
 pub struct Mastodon(pub Url);
 
+thread_local!(static REACTOR: Reactor = Reactor::default());
+
+/// Returns the `Reactor` shared by the current thread.
+fn reactor() -> Reactor {
+    REACTOR.with(Reactor::clone)
+}
+
 /// Methods to interact with a Mastodon instance.
 pub trait NodeInstance {
     /// Create a new Mastodon instance.
@@ -21,9 +35,24 @@ pub trait NodeInstance {
     /// Returns the base `Url` of the Mastodon instance.
     fn url(&self) -> Result<Url>;
     /// Returns a Client for the API of the Mastodon instance.
-    fn client(&self, token: &str) -> Result<Client>;
+    ///
+    /// `token` is the Bearer token, such as an `AccessToken`.
+    fn client<T: AsRef<str>>(&self, token: T) -> Result<Client>;
+    /// Fetches and parses the version of the Mastodon instance, with an unauthenticated request.
+    fn version(&self) -> Result<Version>;
+    /// Returns a Client for the API of the Mastodon instance, which knows the instance version
+    /// and fails fast on the methods it does not support.
+    fn versioned_client<T: AsRef<str>>(&self, token: T) -> Result<Client>;
+    /// Returns an AsyncClient for the API of the Mastodon instance, running on `handle`.
+    fn async_client<T: AsRef<str>>(&self, token: T, handle: &Handle) -> Result<AsyncClient>;
     /// Register a 3rd-party App with the Mastodon instance.
     fn register_app(&self, app: CreateApp) -> Result<OAuthApp>;
+    /// Returns the URL where a user can authorize a registered App for `scopes`.
+    fn authorize_url(&self, app: &OAuthApp, scopes: &str) -> Result<Url>;
+    /// Requests an access token for a registered App, with the given grant and `scopes`.
+    fn fetch_token(&self, app: &OAuthApp, grant: AuthorizationType, scopes: &str) -> Result<AccessToken>;
+    /// Revokes an access token of a registered App.
+    fn revoke_token(&self, app: &OAuthApp, token: &str) -> Result<()>;
 }
 
 impl NodeInstance for Mastodon {
@@ -33,21 +62,44 @@ impl NodeInstance for Mastodon {
     fn url(&self) -> Result<Url> {
         Ok(self.0.clone())
     }
-    fn client(&self, token: &str) -> Result<Client> {
-        Ok(Client {
-            url_base: self.url().chain_err(|| "Could not set the base URL")?,
-            token: Bearer::from_str(token).chain_err(|| "Could not parse Bearer Token")?
-        })
+    fn client<T: AsRef<str>>(&self, token: T) -> Result<Client> {
+        let url_base = self.url().chain_err(|| "Could not set the base URL")?;
+        let token = Bearer::from_str(token.as_ref()).chain_err(|| "Could not parse Bearer Token")?;
+        Ok(Client::new(url_base, token, reactor()))
+    }
+    fn version(&self) -> Result<Version> {
+        let instance = Instances::get_instance(&self.client("")?)
+            .chain_err(|| "Could not fetch instance version.")?;
+        Version::from_str(&instance.version)
+    }
+    fn versioned_client<T: AsRef<str>>(&self, token: T) -> Result<Client> {
+        Ok(self.client(token)?.with_version(self.version()?))
+    }
+    fn async_client<T: AsRef<str>>(&self, token: T, handle: &Handle) -> Result<AsyncClient> {
+        Ok(AsyncClient::new(self.client(token)?, handle))
     }
     fn register_app(&self, app: CreateApp) -> Result<OAuthApp> {
         let out = Arc::new(Mutex::new(Vec::new()));
         app
-            .register(self.endpoint_url("/api/v1/apps")?.as_str(), out.clone())
+            .register(self.endpoint_url("/api/v1/apps")?.as_str(), out.clone(), &reactor())
             .chain_err(|| "Could not register App.")?;
         let out = out.lock().unwrap();
         if out.is_empty() { bail!("Invalid result. Empty") }
         serde_json::from_slice(&out).chain_err(|| "Unexpected JSON error.")
     }
+    fn authorize_url(&self, app: &OAuthApp, scopes: &str) -> Result<Url> {
+        app.authorize_url(&self.0, scopes)
+    }
+    fn fetch_token(&self, app: &OAuthApp, grant: AuthorizationType, scopes: &str) -> Result<AccessToken> {
+        let token_url = self.endpoint_url("/oauth/token")?;
+        reactor().run(|http, _| app.request_token(token_url.as_str(), grant, scopes, http))
+            .chain_err(|| "Could not fetch access token.")
+    }
+    fn revoke_token(&self, app: &OAuthApp, token: &str) -> Result<()> {
+        let revoke_url = self.endpoint_url("/oauth/revoke")?;
+        reactor().run(|http, _| app.revoke_token(revoke_url.as_str(), token, http))
+            .chain_err(|| "Could not revoke access token.")
+    }
 }
 
 pub trait ApiHandler {
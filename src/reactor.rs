@@ -0,0 +1,52 @@
+//! This module contains the event loop and HTTPS client that run the blocking requests.
+//!
+use errors::*;
+use futures::Future;
+use hyper::Client as WebClient;
+use hyper::client::HttpConnector;
+use hyper_tls::HttpsConnector;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use tokio_core::reactor::{Core, Handle};
+
+/// The HTTPS client used for every request, keeping a pool of connections alive.
+pub type HttpsClient = WebClient<HttpsConnector<HttpConnector>>;
+
+/// Builds a new `HttpsClient`, with its own connection pool, running on `handle`.
+pub fn https_client(handle: &Handle) -> HttpsClient {
+    WebClient::configure()
+        .connector(HttpsConnector::new(4, handle))
+        .build(handle)
+}
+
+/// An event loop and `HttpsClient` for running blocking requests.
+///
+/// Both are started by the first request, and are then shared by the clones of the `Reactor`,
+/// so that the following requests reuse the event loop and the pooled connections.
+#[derive(Clone, Default)]
+pub struct Reactor(Rc<RefCell<Option<(Core, HttpsClient)>>>);
+
+impl Reactor {
+    /// Runs the future built by `work` with the shared `HttpsClient`, blocking until it resolves.
+    pub fn run<T, F>(&self, work: F) -> Result<T>
+        where F: FnOnce(&HttpsClient, &Handle) -> Box<Future<Item = T, Error = Error>>
+    {
+        let mut reactor = self.0.borrow_mut();
+        if reactor.is_none() {
+            let core = Core::new().chain_err(|| "Could not start client reactor")?;
+            let http = https_client(&core.handle());
+            *reactor = Some((core, http));
+        }
+        let (ref mut core, ref http) = *reactor.as_mut().unwrap();
+        let work = work(http, &core.handle());
+        core.run(work)
+    }
+}
+
+impl fmt::Debug for Reactor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let started = self.0.borrow().is_some();
+        f.debug_struct("Reactor").field("started", &started).finish()
+    }
+}
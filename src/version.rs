@@ -0,0 +1,148 @@
+//! This module contains the parsing of instance versions, and the capabilities they support.
+//!
+use errors::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Features of the Mastodon API that are only supported from some version onwards.
+///
+/// `SearchV2`, `MediaV2` and `InstanceV2` are checked by the endpoints that require them, which
+/// fail fast on older instances. `Polls`, `Bookmarks` and `StatusEdits` are only for queries with
+/// `Client::supports`, since no endpoint of herder requires them yet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Capability {
+    /// `GET /api/v2/search`, since Mastodon 2.4.1.
+    SearchV2,
+    /// Polls attached to statuses, since Mastodon 2.8.0.
+    Polls,
+    /// Bookmarked statuses, since Mastodon 3.1.0.
+    Bookmarks,
+    /// `POST /api/v2/media`, since Mastodon 3.1.3.
+    MediaV2,
+    /// Editing statuses, since Mastodon 3.5.0.
+    StatusEdits,
+    /// `GET /api/v2/instance`, since Mastodon 4.0.0.
+    InstanceV2
+}
+
+impl Capability {
+    /// Returns the first Mastodon version with the capability, as `(major, minor, patch)`.
+    pub fn since(&self) -> (u64, u64, u64) {
+        match *self {
+            Capability::SearchV2 => (2, 4, 1),
+            Capability::Polls => (2, 8, 0),
+            Capability::Bookmarks => (3, 1, 0),
+            Capability::MediaV2 => (3, 1, 3),
+            Capability::StatusEdits => (3, 5, 0),
+            Capability::InstanceV2 => (4, 0, 0)
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Capability::SearchV2 => "v2 search",
+            Capability::Polls => "polls",
+            Capability::Bookmarks => "bookmarks",
+            Capability::MediaV2 => "v2 media uploads",
+            Capability::StatusEdits => "status edits",
+            Capability::InstanceV2 => "v2 instance information"
+        };
+        f.write_str(name)
+    }
+}
+
+/// The version of a Mastodon instance, as reported by `GET /api/v1/instance`.
+///
+/// Besides plain versions such as `3.5.3`, this parses the versions reported by
+/// release candidates (`4.0.0rc1`), by forks of Mastodon (`4.0.2+glitch`) and by other
+/// software compatible with the Mastodon API (`2.7.2 (compatible; Pleroma 2.4.0)`). The
+/// `major`, `minor` and `patch` numbers are always those of the Mastodon API.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release suffix, such as `rc1` or `-beta.1`.
+    pub pre: Option<String>,
+    /// Build metadata, such as `glitch`.
+    pub build: Option<String>,
+    /// The software compatible with the Mastodon API, such as `Pleroma 2.4.0`.
+    pub compatible: Option<String>
+}
+
+impl Version {
+    /// Returns whether this version is at least `major.minor.patch`.
+    pub fn at_least(&self, major: u64, minor: u64, patch: u64) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+
+    /// Returns whether this version supports `capability`.
+    pub fn supports(&self, capability: Capability) -> bool {
+        let (major, minor, patch) = capability.since();
+        self.at_least(major, minor, patch)
+    }
+
+    /// Fails with `ErrorKind::Unsupported` when this version does not support `capability`.
+    pub fn require(&self, capability: Capability) -> Result<()> {
+        if !self.supports(capability) {
+            bail!(ErrorKind::Unsupported(capability.to_string(), self.to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Version> {
+        let s = s.trim();
+        let (version, compatible) = match s.find(" (compatible; ") {
+            Some(i) => {
+                let compatible = s[i + 14..].trim_right_matches(')').trim();
+                (&s[..i], Some(String::from(compatible)))
+            },
+            None => (s, None)
+        };
+        let (version, build) = match version.find('+') {
+            Some(i) => (&version[..i], Some(String::from(&version[i + 1..]))),
+            None => (version, None)
+        };
+        let mut numbers = version.splitn(3, '.');
+        let major = parse_number(numbers.next(), s)?;
+        let minor = parse_number(numbers.next(), s)?;
+        let (patch, pre) = match numbers.next() {
+            Some(rest) => {
+                let digits = rest.find(|c: char| !c.is_digit(10)).unwrap_or_else(|| rest.len());
+                let pre = &rest[digits..];
+                let pre = if pre.is_empty() { None } else { Some(String::from(pre)) };
+                (parse_number(Some(&rest[..digits]), s)?, pre)
+            },
+            None => (0, None)
+        };
+        Ok(Version { major, minor, patch, pre, build, compatible })
+    }
+}
+
+fn parse_number(number: Option<&str>, version: &str) -> Result<u64> {
+    number
+        .and_then(|number| u64::from_str(number).ok())
+        .ok_or_else(|| Error::from(format!("Invalid instance version: {}", version)))
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(ref pre) = self.pre {
+            write!(f, "{}", pre)?;
+        }
+        if let Some(ref build) = self.build {
+            write!(f, "+{}", build)?;
+        }
+        if let Some(ref compatible) = self.compatible {
+            write!(f, " (compatible; {})", compatible)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+//! This module contains a WebFinger client, to look up `user@domain` accounts on their own host.
+//!
+use errors::*;
+use futures::{future, Future, Stream};
+use hyper::{Body, Request, Uri};
+use hyper::Method::Get;
+use reactor::{HttpsClient, Reactor};
+use serde_json;
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+/// Relation of the link to the HTML profile of an account.
+pub const PROFILE_PAGE_REL: &str = "http://webfinger.net/rel/profile-page";
+
+/// Relation of the link to the ActivityPub actor of an account.
+pub const SELF_REL: &str = "self";
+
+/// A `user@domain` account handle.
+///
+/// Parses `user@domain`, `@user@domain` and `acct:user@domain`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Acct {
+    pub username: String,
+    pub domain: String
+}
+
+impl Acct {
+    /// Returns the `acct:` URI of the account.
+    pub fn uri(&self) -> String {
+        format!("acct:{}", self)
+    }
+
+    /// Returns whether the `acct` of an `Account` on `local_domain` names this account.
+    ///
+    /// Local accounts are named by their username only.
+    pub fn matches(&self, acct: &str, local_domain: &str) -> bool {
+        let acct = acct.to_lowercase();
+        if self.domain.to_lowercase() == local_domain.to_lowercase() && acct == self.username.to_lowercase() {
+            return true;
+        }
+        acct == self.to_string().to_lowercase()
+    }
+
+    /// Returns the WebFinger URL for the account, on its own host.
+    pub fn webfinger_url(&self) -> Result<Url> {
+        let mut url = Url::parse(&format!("https://{}/.well-known/webfinger", self.domain))
+            .chain_err(|| format!("Invalid account domain: {}", self.domain))?;
+        url.query_pairs_mut().append_pair("resource", &self.uri());
+        Ok(url)
+    }
+}
+
+impl FromStr for Acct {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Acct> {
+        let handle = s.trim();
+        let handle = if handle.starts_with("acct:") { &handle[5..] } else { handle };
+        let handle = if handle.starts_with('@') { &handle[1..] } else { handle };
+        let mut parts = handle.splitn(2, '@');
+        match (parts.next(), parts.next()) {
+            (Some(username), Some(domain)) if !username.is_empty() && !domain.is_empty() &&
+                                               !domain.contains(|c: char| c == '@' || c == '/') => {
+                Ok(Acct { username: String::from(username), domain: String::from(domain) })
+            },
+            _ => bail!("Invalid account handle: {}", s)
+        }
+    }
+}
+
+impl fmt::Display for Acct {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.username, self.domain)
+    }
+}
+
+/// A JSON Resource Descriptor, as returned by WebFinger.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Jrd {
+    pub subject: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub links: Vec<Link>
+}
+
+impl Jrd {
+    /// Returns the `Acct` of the subject, when it is an `acct:` URI.
+    pub fn acct(&self) -> Option<Acct> {
+        if !self.subject.starts_with("acct:") {
+            return None;
+        }
+        Acct::from_str(&self.subject).ok()
+    }
+
+    /// Returns the URL of the HTML profile of the account.
+    pub fn profile_page(&self) -> Option<&str> {
+        self.href(PROFILE_PAGE_REL)
+    }
+
+    /// Returns the URI of the ActivityPub actor of the account.
+    pub fn actor(&self) -> Option<&str> {
+        self.href(SELF_REL)
+    }
+
+    /// Returns the `href` of the first link with the relation `rel`.
+    pub fn href(&self, rel: &str) -> Option<&str> {
+        self.links.iter()
+            .filter(|link| link.rel == rel)
+            .filter_map(|link| link.href.as_ref())
+            .map(|href| href.as_str())
+            .next()
+    }
+}
+
+/// A link of a `Jrd`.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Link {
+    pub rel: String,
+    #[serde(rename = "type")]
+    pub link_type: Option<String>,
+    pub href: Option<String>,
+    pub template: Option<String>
+}
+
+/// Looks up `acct` on its own host, blocking on the given `reactor`, and returning its `Jrd`.
+pub fn lookup(acct: &Acct, reactor: &Reactor) -> Result<Jrd> {
+    reactor.run(|http, _| lookup_async(acct, http)).chain_err(|| format!("Failed to look up {}", acct))
+}
+
+/// Looks up `acct` with the `http` client, such as that of an `AsyncClient`, resolving to its
+/// `Jrd`.
+pub fn lookup_async(acct: &Acct, http: &HttpsClient) -> Box<Future<Item = Jrd, Error = Error>> {
+    let uri = match acct.webfinger_url().and_then(|url| {
+        Uri::from_str(url.as_str()).chain_err(|| "Invalid WebFinger URI")
+    }) {
+        Ok(uri) => uri,
+        Err(e) => return Box::new(future::err(e))
+    };
+    let mut req: Request<Body> = Request::new(Get, uri);
+    req.headers_mut().set_raw("Accept", "application/jrd+json");
+
+    let work = http.request(req)
+        .and_then(|res| {
+            let status = res.status();
+            let headers = res.headers().clone();
+            res.body().concat2().map(move |body| (status, headers, body))
+        })
+        .map_err(Error::from)
+        .and_then(|(status, headers, body)| {
+            if !status.is_success() {
+                return Err(Error::from_response(status, &headers, &body));
+            }
+            serde_json::from_slice(&body).chain_err(|| "Unexpected JSON error decoding JRD.")
+        });
+    Box::new(work)
+}
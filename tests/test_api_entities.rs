@@ -1,35 +1,66 @@
+extern crate chrono;
 extern crate herder;
 extern crate serde_json;
 
+use chrono::{DateTime, TimeZone, UTC};
 use herder::api::v1::entities::{
     Account,
     Application,
     Attachment,
+    AttachmentType,
     Card,
     Context,
+    Emoji,
     Error,
+    Field,
     Instance,
+    InstanceConfiguration,
+    InstanceContact,
+    InstanceStats,
+    InstanceUrls,
+    InstanceV2,
+    MediaConfiguration,
     Mention,
     Notification,
+    NotificationType,
+    PollOption,
+    PollsConfiguration,
     Relationship,
     Report,
     Results,
+    ResultsV2,
+    Rule,
     Status,
-    Tag
+    StatusesConfiguration,
+    Tag,
+    Visibility
 };
+use herder::api::v1::ids::{AccountID, MediaID, NotificationID, PollID, ReportID, StatusID};
+
+fn mock_timestamp() -> DateTime<UTC> {
+    UTC.ymd(2000, 1, 1).and_hms(0, 0, 0)
+}
 
 fn mock_account() -> Account {
     Account {
-        account_id: 1234,
+        account_id: AccountID::from(1234),
         username: String::from("MYUSERNAME"),
         acct: String::from("MYUSERNAME@MYREMOTEDOMAIN"),
         display_name: String::from("jane_sanchez"),
         note: String::from("A self-described person"),
         url: String::from("MYRUL"),
         avatar: String::from("MYURL.png"),
+        avatar_static: None,
         header: String::from("MYHEADER.png"),
         locked: false,
-        created_at: String::from("2000-01-01T00:00:00Z"),
+        emojis: Vec::new(),
+        fields: Vec::new(),
+        bot: false,
+        group: false,
+        moved: None,
+        discoverable: None,
+        created_at: mock_timestamp(),
+        last_status_at: None,
         followers_count: 1234,
         following_count: 1234,
         statuses_count: 1234
@@ -45,26 +76,35 @@ fn mock_application() -> Application {
 
 fn mock_status() -> Status {
     Status {
-        status_id: 1234,
+        status_id: StatusID::from(1234),
         uri: String::from("MYURI"),
-        url: String::from("MYURL"),
+        url: Some(String::from("MYURL")),
         account: mock_account(),
-        in_reply_to_id: Some(1234),
-        in_reply_to_account_id: Some(1234),
+        in_reply_to_id: Some(StatusID::from(1234)),
+        in_reply_to_account_id: Some(AccountID::from(1234)),
         reblog: None,
         content: String::from("My tooted toot!"),
-        created_at: String::from("2000-01-01T00:00:00Z"),
+        created_at: mock_timestamp(),
+        edited_at: None,
+        replies_count: 0,
         reblogs_count: 555,
         favourites_count: 777,
         reblogged: true,
         favourited: true,
+        muted: None,
+        bookmarked: None,
+        pinned: None,
         sensitive: false,
         spoiler_text: String::from(""),
-        visibility: String::from(""),
+        visibility: Visibility::Public,
+        language: None,
         media_attachments: Vec::new(),
         mentions: Vec::new(),
         tags: Vec::new(),
-        application: mock_application()
+        emojis: Vec::new(),
+        poll: None,
+        card: None,
+        application: Some(mock_application())
     }
 }
 
@@ -89,6 +129,135 @@ fn account_deserialize_from_json() {
     assert_eq!(account, mock_account());
 }
 
+#[test]
+fn account_deserialize_from_current_server_json() {
+    let ojson = r#"{
+        "id": "109302436954721982",
+        "username": "herder",
+        "acct": "herder@mastodon.example",
+        "display_name": "Herder :rust:",
+        "locked": false,
+        "bot": true,
+        "discoverable": true,
+        "group": false,
+        "created_at": "2022-11-07T00:00:00.000Z",
+        "note": "<p>Herding toots.</p>",
+        "url": "https://mastodon.example/@herder",
+        "uri": "https://mastodon.example/users/herder",
+        "avatar": "https://files.mastodon.example/accounts/avatars/original/herder.gif",
+        "avatar_static": "https://files.mastodon.example/accounts/avatars/static/herder.png",
+        "header": "https://files.mastodon.example/accounts/headers/original/herder.png",
+        "header_static": "https://files.mastodon.example/accounts/headers/original/herder.png",
+        "followers_count": 42,
+        "following_count": 7,
+        "statuses_count": 1001,
+        "last_status_at": "2023-05-04",
+        "noindex": false,
+        "emojis": [
+            {
+                "shortcode": "rust",
+                "url": "https://files.mastodon.example/custom_emojis/images/original/rust.png",
+                "static_url": "https://files.mastodon.example/custom_emojis/images/static/rust.png",
+                "visible_in_picker": true
+            }
+        ],
+        "roles": [],
+        "fields": [
+            {
+                "name": "Source",
+                "value": "<a href=\"https://github.com/saibatizoku/herder\" rel=\"me\">github.com/saibatizoku/herder</a>",
+                "verified_at": "2022-11-08T12:30:00.000+00:00"
+            },
+            {
+                "name": "Pronouns",
+                "value": "it/its",
+                "verified_at": null
+            }
+        ],
+        "moved": {
+            "id": "1",
+            "username": "herder",
+            "acct": "herder@herder.example",
+            "display_name": "Herder",
+            "locked": false,
+            "created_at": "2017-04-01T00:00:00.000Z",
+            "note": "",
+            "url": "https://herder.example/@herder",
+            "avatar": "https://herder.example/avatars/original/missing.png",
+            "header": "https://herder.example/headers/original/missing.png",
+            "followers_count": 0,
+            "following_count": 0,
+            "statuses_count": 0
+        }
+    }"#;
+    let account: Account = serde_json::from_str(ojson).unwrap();
+    assert_eq!(account.account_id, AccountID::from("109302436954721982"));
+    assert_eq!(account.avatar_static,
+               Some(String::from("https://files.mastodon.example/accounts/avatars/static/herder.png")));
+    assert!(account.bot);
+    assert!(!account.group);
+    assert_eq!(account.discoverable, Some(true));
+    assert_eq!(account.last_status_at, Some(String::from("2023-05-04")));
+    assert_eq!(account.emojis, vec![Emoji {
+        shortcode: String::from("rust"),
+        url: String::from("https://files.mastodon.example/custom_emojis/images/original/rust.png"),
+        static_url: String::from("https://files.mastodon.example/custom_emojis/images/static/rust.png"),
+        visible_in_picker: true,
+        category: None
+    }]);
+    assert_eq!(account.fields, vec![
+        Field {
+            name: String::from("Source"),
+            value: String::from("<a href=\"https://github.com/saibatizoku/herder\" rel=\"me\">\
+                                 github.com/saibatizoku/herder</a>"),
+            verified_at: Some(UTC.ymd(2022, 11, 8).and_hms(12, 30, 0))
+        },
+        Field {
+            name: String::from("Pronouns"),
+            value: String::from("it/its"),
+            verified_at: None
+        }
+    ]);
+    let moved = account.moved.unwrap();
+    assert_eq!(moved.acct, "herder@herder.example");
+    assert!(moved.moved.is_none());
+}
+
+#[test]
+fn account_deserialize_with_string_ids() {
+    let ojson = r#"{
+        "id": "1234",
+        "username": "MYUSERNAME",
+        "acct": "MYUSERNAME@MYREMOTEDOMAIN",
+        "display_name": "jane_sanchez",
+        "note": "A self-described person",
+        "url": "MYRUL",
+        "avatar": "MYURL.png",
+        "header": "MYHEADER.png",
+        "locked": false,
+        "created_at": "2000-01-01T00:00:00Z",
+        "followers_count": 1234,
+        "following_count": 1234,
+        "statuses_count": 1234
+    }"#;
+    let account: Account = serde_json::from_str(ojson).unwrap();
+    assert_eq!(account, mock_account());
+}
+
+#[test]
+fn ids_deserialize_from_strings_and_numbers() {
+    let ids: Vec<StatusID> = serde_json::from_str(r#"[1234, "1234", "9zC4kHq1jAMyNbG2y8"]"#).unwrap();
+    assert_eq!(ids, vec![StatusID::from(1234), StatusID::from("1234"), StatusID::from("9zC4kHq1jAMyNbG2y8")]);
+    assert!(serde_json::from_str::<StatusID>("null").is_err());
+}
+
+#[test]
+fn ids_serialize_to_strings() {
+    assert_eq!(serde_json::to_string(&AccountID::from(1234)).unwrap(), r#""1234""#);
+    let account = serde_json::to_string(&mock_account()).unwrap();
+    assert_eq!(serde_json::from_str::<Account>(&account).unwrap(), mock_account());
+}
+
 #[test]
 fn application_deserialize_from_json() {
     let ojson = r#"{
@@ -107,16 +276,18 @@ fn attachment_deserialize_from_json() {
                     "url": "MYURL",
                     "remote_url": "MYURL",
                     "preview_url": "MYURL",
-                    "text_url": "MYURL"
+                    "text_url": "MYURL",
+                    "description": "A description"
                  }"#;
     let attachment: Attachment = serde_json::from_str(ojson).unwrap();
     assert_eq!(attachment, Attachment {
-        attachment_id: 1234,
-        attachment_type: String::from("video"),
-        url: String::from("MYURL"),
-        remote_url: String::from("MYURL"),
-        preview_url: String::from("MYURL"),
-        text_url: String::from("MYURL")
+        attachment_id: MediaID::from(1234),
+        attachment_type: AttachmentType::Video,
+        url: Some(String::from("MYURL")),
+        remote_url: Some(String::from("MYURL")),
+        preview_url: Some(String::from("MYURL")),
+        text_url: Some(String::from("MYURL")),
+        description: Some(String::from("A description"))
     });
 }
 
@@ -133,7 +304,7 @@ fn card_deserialize_from_json() {
         url: String::from("MYURL"),
         title: String::from("My Title"),
         description: String::from("My description"),
-        image: String::from("MYIMG.png")
+        image: Some(String::from("MYIMG.png"))
     });
 }
 
@@ -157,27 +328,172 @@ fn error_deserialize_from_json() {
                  }"#;
     let error: Error = serde_json::from_str(ojson).unwrap();
     assert_eq!(error, Error {
-        error: String::from("Error message")
+        error: String::from("Error message"),
+        error_description: None
+    });
+}
+
+#[test]
+fn oauth_error_deserialize_from_json() {
+    let ojson = r#"{
+                    "error": "invalid_grant",
+                    "error_description": "The provided authorization grant is invalid"
+                 }"#;
+    let error: Error = serde_json::from_str(ojson).unwrap();
+    assert_eq!(error, Error {
+        error: String::from("invalid_grant"),
+        error_description: Some(String::from("The provided authorization grant is invalid"))
     });
 }
 
 #[test]
 fn instance_deserialize_from_json() {
     let ojson = r#"{
-                    "url": "MYURL",
+                    "uri": "MYURL",
                     "title": "My Title",
                     "description": "My description",
-                    "email": "MYEMAIL"
+                    "email": "MYEMAIL",
+                    "version": "2.9.0"
                  }"#;
     let instance: Instance = serde_json::from_str(ojson).unwrap();
     assert_eq!(instance, Instance {
-        url: String::from("MYURL"),
+        uri: String::from("MYURL"),
         title: String::from("My Title"),
+        short_description: None,
         description: String::from("My description"),
-        email: String::from("MYEMAIL")
+        email: String::from("MYEMAIL"),
+        version: String::from("2.9.0"),
+        urls: None,
+        stats: None,
+        thumbnail: None,
+        languages: Vec::new(),
+        registrations: None,
+        approval_required: None,
+        configuration: None,
+        contact_account: None,
+        rules: Vec::new()
     });
 }
 
+fn mock_configuration_json() -> &'static str {
+    r#"{
+        "statuses": {
+            "max_characters": 500,
+            "max_media_attachments": 4,
+            "characters_reserved_per_url": 23
+        },
+        "media_attachments": {
+            "supported_mime_types": ["image/png"],
+            "image_size_limit": 10485760,
+            "image_matrix_limit": 16777216,
+            "video_size_limit": 41943040,
+            "video_frame_rate_limit": 60,
+            "video_matrix_limit": 2304000
+        },
+        "polls": {
+            "max_options": 4,
+            "max_characters_per_option": 50,
+            "min_expiration": 300,
+            "max_expiration": 2629746
+        }
+    }"#
+}
+
+fn mock_configuration() -> InstanceConfiguration {
+    InstanceConfiguration {
+        urls: None,
+        statuses: Some(StatusesConfiguration {
+            max_characters: 500,
+            max_media_attachments: 4,
+            characters_reserved_per_url: 23
+        }),
+        media_attachments: Some(MediaConfiguration {
+            supported_mime_types: vec![String::from("image/png")],
+            image_size_limit: 10485760,
+            image_matrix_limit: 16777216,
+            video_size_limit: 41943040,
+            video_frame_rate_limit: 60,
+            video_matrix_limit: 2304000
+        }),
+        polls: Some(PollsConfiguration {
+            max_options: 4,
+            max_characters_per_option: 50,
+            min_expiration: 300,
+            max_expiration: 2629746
+        })
+    }
+}
+
+#[test]
+fn instance_with_metadata_deserialize_from_json() {
+    let ojson = format!(r#"{{
+                    "uri": "mastodon.example",
+                    "title": "My Title",
+                    "short_description": "Short",
+                    "description": "My description",
+                    "email": "MYEMAIL",
+                    "version": "3.5.3",
+                    "urls": {{ "streaming_api": "wss://mastodon.example" }},
+                    "stats": {{ "user_count": 10, "status_count": 200, "domain_count": 3000 }},
+                    "thumbnail": "MYTHUMBNAIL.png",
+                    "languages": ["en", "es"],
+                    "registrations": false,
+                    "approval_required": true,
+                    "configuration": {},
+                    "contact_account": null,
+                    "rules": [{{ "id": "1", "text": "Be nice" }}]
+                 }}"#, mock_configuration_json());
+    let instance: Instance = serde_json::from_str(&ojson).unwrap();
+    assert_eq!(instance.urls, Some(InstanceUrls { streaming_api: String::from("wss://mastodon.example") }));
+    assert_eq!(instance.stats, Some(InstanceStats { user_count: 10, status_count: 200, domain_count: 3000 }));
+    assert_eq!(instance.languages, vec![String::from("en"), String::from("es")]);
+    assert_eq!(instance.configuration, Some(mock_configuration()));
+    assert_eq!(instance.rules, vec![Rule { id: String::from("1"), text: String::from("Be nice") }]);
+}
+
+#[test]
+fn instance_v2_deserialize_from_json() {
+    let ojson = format!(r#"{{
+                    "domain": "mastodon.example",
+                    "title": "My Title",
+                    "version": "4.0.2",
+                    "source_url": "https://github.com/mastodon/mastodon",
+                    "description": "My description",
+                    "usage": {{ "users": {{ "active_month": 42 }} }},
+                    "thumbnail": {{ "url": "MYTHUMBNAIL.png" }},
+                    "languages": ["en"],
+                    "configuration": {},
+                    "registrations": {{ "enabled": true, "approval_required": false, "message": null }},
+                    "contact": {{
+                        "email": "MYEMAIL",
+                        "account": {{
+                            "id": 1234,
+                            "username": "MYUSERNAME",
+                            "acct": "MYUSERNAME@MYREMOTEDOMAIN",
+                            "display_name": "jane_sanchez",
+                            "note": "A self-described person",
+                            "url": "MYRUL",
+                            "avatar": "MYURL.png",
+                            "header": "MYHEADER.png",
+                            "locked": false,
+                            "created_at": "2000-01-01T00:00:00Z",
+                            "followers_count": 1234,
+                            "following_count": 1234,
+                            "statuses_count": 1234
+                        }}
+                    }},
+                    "rules": []
+                 }}"#, mock_configuration_json());
+    let instance: InstanceV2 = serde_json::from_str(&ojson).unwrap();
+    assert_eq!(instance.version, "4.0.2");
+    assert_eq!(instance.usage.unwrap().users.active_month, 42);
+    assert_eq!(instance.configuration, mock_configuration());
+    assert_eq!(instance.contact, Some(InstanceContact {
+        email: String::from("MYEMAIL"),
+        account: Some(mock_account())
+    }));
+}
+
 #[test]
 fn mention_deserialize_from_json() {
     let ojson = r#"{
@@ -188,7 +504,7 @@ fn mention_deserialize_from_json() {
                  }"#;
     let mention: Mention = serde_json::from_str(ojson).unwrap();
     assert_eq!(mention, Mention {
-        mention_id: 1234,
+        mention_id: AccountID::from(1234),
         url: String::from("MYURL"),
         username: String::from("MYUSERNAME"),
         acct: String::from("MYUSERNAME@REMOTEDOMAIN")
@@ -199,7 +515,7 @@ fn mention_deserialize_from_json() {
 fn notification_deserialize_from_json() {
     let ojson = r#"{
                     "id": 1234,
-                    "type": "NOTIFICATIONTYPE",
+                    "type": "mention",
                     "created_at": "2000-01-01T00:00:00Z",
                     "account": {
                         "id": 1234,
@@ -220,9 +536,9 @@ fn notification_deserialize_from_json() {
                  }"#;
     let notification: Notification = serde_json::from_str(ojson).unwrap();
     assert_eq!(notification, Notification {
-        notification_id: 1234,
-        notification_type: String::from("NOTIFICATIONTYPE"),
-        created_at: String::from("2000-01-01T00:00:00Z"),
+        notification_id: NotificationID::from(1234),
+        notification_type: NotificationType::Mention,
+        created_at: mock_timestamp(),
         account: mock_account(),
         status: None
     });
@@ -240,7 +556,7 @@ fn relationship_deserialize_from_json() {
                  }"#;
     let relationship: Relationship = serde_json::from_str(ojson).unwrap();
     assert_eq!(relationship, Relationship {
-        relationship_id: 1234,
+        relationship_id: AccountID::from(1234),
         following: true,
         followed_by: true,
         blocking: true,
@@ -252,14 +568,53 @@ fn relationship_deserialize_from_json() {
 #[test]
 fn report_deserialize_from_json() {
     let ojson = r#"{
-                    "id": 1234,
-                    "action_taken": "Some action taken"
+                    "id": "48914",
+                    "action_taken": false,
+                    "action_taken_at": null,
+                    "category": "spam",
+                    "comment": "Spam account",
+                    "forwarded": false,
+                    "created_at": "2022-08-25T09:56:16.763Z",
+                    "status_ids": ["108882889550545820"],
+                    "rule_ids": null,
+                    "target_account": {
+                        "id": 1234,
+                        "username": "MYUSERNAME",
+                        "acct": "MYUSERNAME@MYREMOTEDOMAIN",
+                        "display_name": "jane_sanchez",
+                        "note": "A self-described person",
+                        "url": "MYRUL",
+                        "avatar": "MYURL.png",
+                        "header": "MYHEADER.png",
+                        "locked": false,
+                        "created_at": "2000-01-01T00:00:00Z",
+                        "followers_count": 1234,
+                        "following_count": 1234,
+                        "statuses_count": 1234
+                    }
                  }"#;
     let report: Report = serde_json::from_str(ojson).unwrap();
     assert_eq!(report, Report {
-        report_id: 1234,
-        action_taken: String::from("Some action taken")
+        report_id: ReportID::from(48914),
+        action_taken: false,
+        action_taken_at: None,
+        category: String::from("spam"),
+        comment: String::from("Spam account"),
+        forwarded: false,
+        created_at: Some(UTC.ymd(2022, 8, 25).and_hms_milli(9, 56, 16, 763)),
+        status_ids: vec![StatusID::from("108882889550545820")],
+        target_account: Some(mock_account())
     });
+    let encoded = serde_json::to_string(&report).unwrap();
+    assert_eq!(serde_json::from_str::<Report>(&encoded).unwrap(), report);
+}
+
+#[test]
+fn report_deserialize_from_legacy_json() {
+    let ojson = r#"{"id": 1234, "action_taken": true}"#;
+    let report: Report = serde_json::from_str(ojson).unwrap();
+    assert!(report.action_taken);
+    assert_eq!(report.target_account, None);
 }
 
 #[test]
@@ -277,6 +632,29 @@ fn results_deserialize_from_json() {
     });
 }
 
+#[test]
+fn results_v2_deserialize_from_json() {
+    let ojson = r#"{
+                    "accounts": [],
+                    "statuses": [],
+                    "hashtags": [
+                        {
+                            "name": "rustlang",
+                            "url": "MYURL"
+                        }
+                    ]
+                 }"#;
+    let results: ResultsV2 = serde_json::from_str(ojson).unwrap();
+    assert_eq!(results, ResultsV2 {
+        accounts: Vec::new(),
+        statuses: Vec::new(),
+        hashtags: vec![Tag {
+            name: String::from("rustlang"),
+            url: String::from("MYURL")
+        }]
+    });
+}
+
 #[test]
 fn status_deserialize_from_json() {
     let ojson = r#"{
@@ -309,7 +687,7 @@ fn status_deserialize_from_json() {
                     "favourited": true,
                     "sensitive": false,
                     "spoiler_text": "",
-                    "visibility": "",
+                    "visibility": "public",
                     "media_attachments": [],
                     "mentions": [],
                     "tags": [],
@@ -322,6 +700,182 @@ fn status_deserialize_from_json() {
     assert_eq!(status, mock_status());
 }
 
+#[test]
+fn status_deserialize_from_current_server_json() {
+    let ojson = r#"{
+        "id": "110316537225497436",
+        "created_at": "2023-05-04T18:03:21.123Z",
+        "in_reply_to_id": null,
+        "in_reply_to_account_id": null,
+        "sensitive": false,
+        "spoiler_text": "",
+        "visibility": "unlisted",
+        "language": "en",
+        "uri": "https://mastodon.example/users/herder/statuses/110316537225497436",
+        "url": "https://mastodon.example/@herder/110316537225497436",
+        "replies_count": 3,
+        "reblogs_count": 5,
+        "favourites_count": 8,
+        "edited_at": "2023-05-04T18:10:00.000Z",
+        "favourited": false,
+        "reblogged": false,
+        "muted": false,
+        "bookmarked": true,
+        "pinned": false,
+        "content": "<p>Which crate? :rust:</p>",
+        "filtered": [],
+        "reblog": null,
+        "application": null,
+        "account": {
+            "id": "109302436954721982",
+            "username": "herder",
+            "acct": "herder",
+            "display_name": "Herder",
+            "locked": false,
+            "bot": false,
+            "discoverable": null,
+            "group": false,
+            "created_at": "2022-11-07T00:00:00.000Z",
+            "note": "",
+            "url": "https://mastodon.example/@herder",
+            "avatar": "https://files.mastodon.example/accounts/avatars/original/herder.png",
+            "avatar_static": "https://files.mastodon.example/accounts/avatars/original/herder.png",
+            "header": "https://mastodon.example/headers/original/missing.png",
+            "header_static": "https://mastodon.example/headers/original/missing.png",
+            "followers_count": 42,
+            "following_count": 7,
+            "statuses_count": 1001,
+            "last_status_at": "2023-05-04",
+            "emojis": [],
+            "fields": []
+        },
+        "media_attachments": [],
+        "mentions": [],
+        "tags": [],
+        "emojis": [
+            {
+                "shortcode": "rust",
+                "url": "https://files.mastodon.example/custom_emojis/images/original/rust.png",
+                "static_url": "https://files.mastodon.example/custom_emojis/images/static/rust.png",
+                "visible_in_picker": true,
+                "category": "Languages"
+            }
+        ],
+        "card": {
+            "url": "https://crates.io/crates/herder",
+            "title": "herder - crates.io",
+            "description": "A client for Mastodon",
+            "type": "link",
+            "author_name": "",
+            "provider_name": "crates.io",
+            "image": "https://files.mastodon.example/cache/preview_cards/images/herder.png",
+            "width": 400,
+            "height": 400
+        },
+        "poll": {
+            "id": "34830",
+            "expires_at": "2023-05-05T18:03:21.000Z",
+            "expired": false,
+            "multiple": false,
+            "votes_count": 10,
+            "voters_count": 10,
+            "voted": true,
+            "own_votes": [1],
+            "options": [
+                {
+                    "title": "herder",
+                    "votes_count": 6
+                },
+                {
+                    "title": "the other one",
+                    "votes_count": 4
+                }
+            ],
+            "emojis": []
+        }
+    }"#;
+    let status: Status = serde_json::from_str(ojson).unwrap();
+    assert_eq!(status.status_id, StatusID::from("110316537225497436"));
+    assert_eq!(status.created_at, UTC.ymd(2023, 5, 4).and_hms_milli(18, 3, 21, 123));
+    assert_eq!(status.edited_at, Some(UTC.ymd(2023, 5, 4).and_hms(18, 10, 0)));
+    assert_eq!(status.visibility, Visibility::Unlisted);
+    assert_eq!(status.language, Some(String::from("en")));
+    assert_eq!(status.replies_count, 3);
+    assert_eq!(status.muted, Some(false));
+    assert_eq!(status.bookmarked, Some(true));
+    assert_eq!(status.pinned, Some(false));
+    assert_eq!(status.application, None);
+    assert_eq!(status.account.avatar_static,
+               Some(String::from("https://files.mastodon.example/accounts/avatars/original/herder.png")));
+    assert_eq!(status.account.discoverable, None);
+    assert_eq!(status.emojis.len(), 1);
+    assert_eq!(status.emojis[0].category, Some(String::from("Languages")));
+    assert_eq!(status.card, Some(Card {
+        url: String::from("https://crates.io/crates/herder"),
+        title: String::from("herder - crates.io"),
+        description: String::from("A client for Mastodon"),
+        image: Some(String::from("https://files.mastodon.example/cache/preview_cards/images/herder.png"))
+    }));
+    let poll = status.poll.unwrap();
+    assert_eq!(poll.poll_id, PollID::from(34830));
+    assert_eq!(poll.expires_at, Some(UTC.ymd(2023, 5, 5).and_hms(18, 3, 21)));
+    assert!(!poll.expired);
+    assert_eq!(poll.votes_count, 10);
+    assert_eq!(poll.voters_count, Some(10));
+    assert_eq!(poll.voted, Some(true));
+    assert_eq!(poll.own_votes, Some(vec![1]));
+    assert_eq!(poll.options, vec![
+        PollOption { title: String::from("herder"), votes_count: Some(6) },
+        PollOption { title: String::from("the other one"), votes_count: Some(4) }
+    ]);
+}
+
+#[test]
+fn status_serialize_round_trip() {
+    let status = mock_status();
+    let json = serde_json::to_string(&status).unwrap();
+    assert!(json.contains(r#""created_at":"2000-01-01T00:00:00Z""#));
+    let round_trip: Status = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_trip, status);
+}
+
+#[test]
+fn timestamps_deserialize_with_fractions_and_offsets() {
+    let utc: DateTime<UTC> = serde_json::from_str(r#""2019-12-05T11:34:47.196Z""#).unwrap();
+    assert_eq!(utc, UTC.ymd(2019, 12, 5).and_hms_milli(11, 34, 47, 196));
+    let offset: DateTime<UTC> = serde_json::from_str(r#""2019-12-05T12:34:47.196+01:00""#).unwrap();
+    assert_eq!(offset, utc);
+    assert!(serde_json::from_str::<DateTime<UTC>>(r#""yesterday""#).is_err());
+    assert!(mock_timestamp() < utc);
+}
+
+#[test]
+fn string_enums_deserialize_from_known_and_unknown_values() {
+    let visibility: Vec<Visibility> = serde_json::from_str(r#"["public", "unlisted", "private", "direct", "local"]"#).unwrap();
+    assert_eq!(visibility, vec![Visibility::Public,
+                                Visibility::Unlisted,
+                                Visibility::Private,
+                                Visibility::Direct,
+                                Visibility::Other(String::from("local"))]);
+    let attachment_type: Vec<AttachmentType> = serde_json::from_str(r#"["image", "gifv", "unknown", "3d"]"#).unwrap();
+    assert_eq!(attachment_type, vec![AttachmentType::Image,
+                                     AttachmentType::Gifv,
+                                     AttachmentType::Unknown,
+                                     AttachmentType::Other(String::from("3d"))]);
+    let notification_type: Vec<NotificationType> = serde_json::from_str(r#"["follow_request", "update", "admin.sign_up"]"#).unwrap();
+    assert_eq!(notification_type, vec![NotificationType::FollowRequest,
+                                       NotificationType::Update,
+                                       NotificationType::Other(String::from("admin.sign_up"))]);
+}
+
+#[test]
+fn string_enums_serialize_to_api_values() {
+    assert_eq!(serde_json::to_string(&Visibility::Direct).unwrap(), r#""direct""#);
+    assert_eq!(serde_json::to_string(&AttachmentType::Audio).unwrap(), r#""audio""#);
+    assert_eq!(serde_json::to_string(&NotificationType::Other(String::from("admin.report"))).unwrap(),
+               r#""admin.report""#);
+}
+
 #[test]
 fn tag_deserialize_from_json() {
     let ojson = r#"{
@@ -2,6 +2,16 @@ extern crate herder;
 extern crate hyper;
 
 use herder::api::APIMethodRequest;
+use herder::api::v1::entities::Visibility;
+use herder::api::v1::methods::{
+    AccountID,
+    MediaID,
+    MediaUpload,
+    NewReport,
+    NewStatus,
+    StatusID,
+    UserFormData
+};
 use hyper::Method::{Get};
 use hyper::{Headers, Uri};
 use std::str::FromStr;
@@ -17,3 +27,153 @@ fn default_api_method() {
     };
     assert_eq!(default, APIMethodRequest::default())
 }
+
+#[test]
+fn new_status_form_encode() {
+    let status = NewStatus::new("My tooted toot!")
+        .in_reply_to(StatusID::from(1234))
+        .media(MediaID::from(1))
+        .media(MediaID::from(2))
+        .sensitive(true)
+        .spoiler_text("toot")
+        .visibility(Visibility::Unlisted)
+        .language("en");
+    assert_eq!(status.form_encode().unwrap(),
+               "status=My+tooted+toot%21&in_reply_to_id=1234&media_ids%5B%5D=1&media_ids%5B%5D=2\
+                &sensitive=true&spoiler_text=toot&visibility=unlisted&language=en");
+}
+
+#[test]
+fn new_status_with_media_only() {
+    let status = NewStatus::new("").media(MediaID::from(1));
+    assert!(status.validate().is_ok());
+}
+
+#[test]
+fn new_status_without_text_or_media_is_rejected() {
+    assert!(NewStatus::new("").validate().is_err());
+    assert!(NewStatus::new("   ").validate().is_err());
+}
+
+#[test]
+fn new_status_with_too_many_media_is_rejected() {
+    let status = (1..6).fold(NewStatus::new("toot"), |status, id| status.media(MediaID::from(id)));
+    assert!(status.validate().is_err());
+}
+
+#[test]
+fn new_status_with_other_visibility_is_sent_as_is() {
+    let status = NewStatus::new("toot").visibility(Visibility::Other(String::from("local")));
+    assert_eq!(status.form_encode().unwrap(), "status=toot&visibility=local");
+}
+
+#[test]
+fn media_upload_multipart() {
+    let media = MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec())
+        .description("A cat")
+        .focus(0.5, -0.25);
+    let form = media.multipart().unwrap();
+    let boundary = String::from(form.boundary());
+    assert_eq!(form.content_type(), format!("multipart/form-data; boundary={}", boundary));
+    let body = String::from_utf8(form.finish()).unwrap();
+    assert_eq!(body, format!("--{b}\r\n\
+                              Content-Disposition: form-data; name=\"file\"; filename=\"cat.png\"\r\n\
+                              Content-Type: image/png\r\n\r\n\
+                              PNG\r\n\
+                              --{b}\r\n\
+                              Content-Disposition: form-data; name=\"description\"\r\n\r\n\
+                              A cat\r\n\
+                              --{b}\r\n\
+                              Content-Disposition: form-data; name=\"focus\"\r\n\r\n\
+                              0.5,-0.25\r\n\
+                              --{b}--\r\n", b = boundary));
+}
+
+#[test]
+fn media_upload_with_invalid_mime_type_is_rejected() {
+    let media = MediaUpload::from_bytes("cat.png", "image/png\r\nX-Injected: 1", b"PNG".to_vec());
+    assert!(media.multipart().is_err());
+    assert!(MediaUpload::from_bytes("cat.png", "image", b"PNG".to_vec()).multipart().is_err());
+    assert!(MediaUpload::from_bytes("cat.svg", "image/svg+xml", b"SVG".to_vec()).multipart().is_ok());
+}
+
+#[test]
+fn media_upload_from_reader() {
+    let media = MediaUpload::from_reader("cat.png", "image/png", &b"PNG"[..]).unwrap();
+    assert_eq!(media, MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec()));
+}
+
+#[test]
+fn media_upload_rejects_empty_media() {
+    let media = MediaUpload::from_bytes("cat.png", "image/png", Vec::new());
+    assert!(media.multipart().is_err());
+}
+
+#[test]
+fn media_upload_rejects_invalid_focus() {
+    let media = MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec()).focus(1.5, 0.0);
+    assert!(media.multipart().is_err());
+}
+
+#[test]
+fn user_form_data_form_encode() {
+    let form_data = UserFormData::new(Some(String::from("Jane")), Some(String::from("A person")), None, None)
+        .locked(true)
+        .bot(false)
+        .discoverable(true)
+        .field("Website", "https://example.com");
+    assert_eq!(form_data.form_encode().unwrap(),
+               "display_name=Jane&note=A+person&locked=true&bot=false&discoverable=true\
+                &fields_attributes%5B0%5D%5Bname%5D=Website\
+                &fields_attributes%5B0%5D%5Bvalue%5D=https%3A%2F%2Fexample.com");
+}
+
+#[test]
+fn user_form_data_rejects_too_many_fields() {
+    let form_data = UserFormData::new(None, None, None, None)
+        .field("1", "a")
+        .field("2", "b")
+        .field("3", "c")
+        .field("4", "d")
+        .field("5", "e");
+    assert!(form_data.form_encode().is_err());
+}
+
+#[test]
+fn user_form_data_multipart_with_images() {
+    let form_data = UserFormData::new(Some(String::from("Jane")), None, None, None)
+        .header(MediaUpload::from_bytes("header.jpg", "image/jpeg", b"JPG".to_vec()));
+    assert!(form_data.has_images());
+    assert!(form_data.form_encode().is_err());
+    let form = form_data.multipart().unwrap();
+    let boundary = String::from(form.boundary());
+    let body = String::from_utf8(form.finish()).unwrap();
+    assert_eq!(body, format!("--{b}\r\n\
+                              Content-Disposition: form-data; name=\"display_name\"\r\n\r\n\
+                              Jane\r\n\
+                              --{b}\r\n\
+                              Content-Disposition: form-data; name=\"header\"; filename=\"header.jpg\"\r\n\
+                              Content-Type: image/jpeg\r\n\r\n\
+                              JPG\r\n\
+                              --{b}--\r\n", b = boundary));
+}
+
+#[test]
+fn new_report_form_encode() {
+    let report = NewReport::new(AccountID::from(1234))
+        .status(StatusID::from(1))
+        .status(StatusID::from(2))
+        .comment("Spam!")
+        .forward(true)
+        .category("spam");
+    assert_eq!(report.form_encode().unwrap(),
+               "account_id=1234&status_ids%5B%5D=1&status_ids%5B%5D=2&comment=Spam%21\
+                &forward=true&category=spam");
+}
+
+#[test]
+fn new_report_rejects_long_comment() {
+    let comment: String = ::std::iter::repeat('a').take(1001).collect();
+    let report = NewReport::new(AccountID::from(1234)).comment(&comment);
+    assert!(report.validate().is_err());
+}
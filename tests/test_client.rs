@@ -1,11 +1,17 @@
+extern crate futures;
 extern crate herder;
 extern crate hyper;
+extern crate tokio_core;
 extern crate url;
 
+use futures::Future;
 use herder::{Client, Mastodon};
+use herder::api::v1::methods::{AccountID, Accounts};
 use herder::mastodon::{NodeInstance, ApiHandler};
 use hyper::header::Bearer;
+use std::net::TcpListener;
 use std::str::FromStr;
+use tokio_core::reactor::Core;
 use url::Url;
 
 const BASE_URL: &str = "http://localhost:3000";
@@ -39,3 +45,33 @@ fn client_builds_api_endpoint_url() {
     let api_endpoint = client.endpoint_url("/api/v1/").unwrap();
     assert_eq!(api_endpoint, Url::parse(&format!("{}/api/v1/", BASE_URL)).unwrap());
 }
+
+#[test]
+fn create_async_client_with_mastodon_domain() {
+    let core = Core::new().unwrap();
+    let mastodon = Mastodon::new(BASE_URL).unwrap();
+    let client = mastodon.async_client(MY_TOKEN, &core.handle()).unwrap();
+    assert_eq!(client.api().url_base, Url::parse(BASE_URL).unwrap());
+    assert_eq!(client.api().token, Bearer::from_str(MY_TOKEN).unwrap());
+}
+
+#[test]
+fn async_client_fails_without_server() {
+    let mut core = Core::new().unwrap();
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let mastodon = Mastodon::new(&format!("http://127.0.0.1:{}", port)).unwrap();
+    let client = mastodon.async_client(MY_TOKEN, &core.handle()).unwrap();
+    let work = client.fetch_account(AccountID::from(1))
+        .join(client.get_current_user());
+    assert!(core.run(work).is_err());
+}
+
+#[test]
+fn blocking_clients_share_the_reactor_of_their_thread() {
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let mastodon = Mastodon::new(&format!("http://127.0.0.1:{}", port)).unwrap();
+    let client = mastodon.client(MY_TOKEN).unwrap();
+    let other_client = mastodon.client(MY_TOKEN).unwrap();
+    assert!(client.fetch_account(AccountID::from(1)).is_err());
+    assert!(format!("{:?}", other_client).contains("Reactor { started: true }"));
+}
@@ -0,0 +1,175 @@
+extern crate herder;
+
+use herder::{Client, Mastodon};
+use herder::api::v1::methods::{
+    Accounts,
+    AccountID,
+    Blocks,
+    Favourites,
+    FollowRequests,
+    Follows,
+    HomeTimelineQuery,
+    Instances,
+    ListID,
+    Media,
+    MediaID,
+    MediaUpload,
+    Mutes,
+    NewReport,
+    NewStatus,
+    NotificationID,
+    Notifications,
+    Reports,
+    NotificationsQuery,
+    PageQuery,
+    RelationshipsQuery,
+    Search,
+    SearchAccountsQuery,
+    SearchContentsQuery,
+    StatusID,
+    Statuses,
+    TimelineQuery,
+    Timelines,
+    UserFormData
+};
+use herder::mastodon::NodeInstance;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+const MY_TOKEN: &str = "MY_TOKEN";
+
+/// Starts a local stand-in for a Mastodon node, answering every request with `response`.
+fn stand_in_server(response: &'static [u8]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break
+            };
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(response);
+        }
+    });
+    format!("http://{}", addr)
+}
+
+/// Returns the URL of a local port with nothing listening on it.
+fn closed_port() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    format!("http://{}", addr)
+}
+
+fn client(base_url: &str) -> Client {
+    Mastodon::new(base_url).unwrap().client(MY_TOKEN).unwrap()
+}
+
+fn assert_every_method_fails(client: &Client) {
+    assert!(client.fetch_account(AccountID::from(1)).is_err());
+    assert!(client.get_current_user().is_err());
+    assert!(client.update_current_user(UserFormData::new(None, None, None, None)).is_err());
+    assert!(client.get_account_followers(AccountID::from(1)).is_err());
+    assert!(client.get_account_following(AccountID::from(1)).is_err());
+    assert!(client.get_account_statutes(AccountID::from(1)).is_err());
+    assert!(client.follow_account(AccountID::from(1)).is_err());
+    assert!(client.unfollow_account(AccountID::from(1)).is_err());
+    assert!(client.block_account(AccountID::from(1)).is_err());
+    assert!(client.unblock_account(AccountID::from(1)).is_err());
+    assert!(client.mute_account(AccountID::from(1)).is_err());
+    assert!(client.unmute_account(AccountID::from(1)).is_err());
+    assert!(client.get_account_relationships(RelationshipsQuery::SingleAccount(AccountID::from(1))).is_err());
+    assert!(client.search_accounts(SearchAccountsQuery { q: String::from("herder"), limit: None }).is_err());
+
+    assert!(client.fetch_status(StatusID::from(1)).is_err());
+    assert!(client.get_status_context(StatusID::from(1)).is_err());
+    assert!(client.get_status_card(StatusID::from(1)).is_err());
+    assert!(client.reblogged_by(StatusID::from(1)).is_err());
+    assert!(client.favourited_by(StatusID::from(1)).is_err());
+    assert!(client.post_status(NewStatus::new("toot")).is_err());
+    assert!(client.reblog_status(StatusID::from(1)).is_err());
+    assert!(client.unreblog_status(StatusID::from(1)).is_err());
+    assert!(client.favourite_status(StatusID::from(1)).is_err());
+    assert!(client.unfavourite_status(StatusID::from(1)).is_err());
+
+    assert!(client.home_timeline(HomeTimelineQuery::default()).is_err());
+    assert!(client.public_timeline(TimelineQuery::default()).is_err());
+    assert!(client.tag_timeline("rustlang", TimelineQuery::default()).is_err());
+    assert!(client.list_timeline(ListID::from(1), HomeTimelineQuery::default()).is_err());
+
+    assert!(client.fetch_notifications(NotificationsQuery::default()).is_err());
+    assert!(client.get_notification(NotificationID::from(1)).is_err());
+
+    assert!(client.fetch_blocks(PageQuery::default()).is_err());
+    assert!(client.fetch_favourites(PageQuery::default()).is_err());
+    assert!(client.fetch_mutes(PageQuery::default()).is_err());
+    assert!(client.fetch_follow_requests(PageQuery::default()).is_err());
+    assert!(client.follow_remote_user("herder@localhost").is_err());
+    assert!(client.authorize_follow_request(AccountID::from(1)).is_err());
+    assert!(client.reject_follow_request(AccountID::from(1)).is_err());
+
+    assert!(client.get_instance().is_err());
+    assert!(client.get_instance_v2().is_err());
+
+    assert!(client.fetching_reports().is_err());
+    assert!(client.reporting_user(NewReport::new(AccountID::from(1))).is_err());
+
+    let query = || SearchContentsQuery { q: String::from("herder"), ..SearchContentsQuery::default() };
+    assert!(client.search_content(query()).is_err());
+    assert!(client.search_content_v2(query()).is_err());
+
+    assert!(client.upload_media(MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec())).is_err());
+    assert!(client.upload_media_v2(MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec())).is_err());
+    assert!(client.get_media(MediaID::from(1)).is_err());
+
+    assert!(client.iter_account_followers(AccountID::from(1), None).next().unwrap().is_err());
+    assert!(client.iter_follow_requests(None).next().unwrap().is_err());
+    assert!(client.iter_blocks(None).next().unwrap().is_err());
+    assert!(client.iter_mutes(None).next().unwrap().is_err());
+    assert!(client.iter_favourites(None).next().unwrap().is_err());
+}
+
+/// Also covers the methods that ignore the response body, which only fail on the request itself.
+fn assert_every_request_fails(client: &Client) {
+    assert_every_method_fails(client);
+    assert!(client.delete_status(StatusID::from(1)).is_err());
+    assert!(client.clear_notifications().is_err());
+    assert!(client.dismiss_notification(NotificationID::from(1)).is_err());
+}
+
+#[test]
+fn client_fails_without_server() {
+    assert_every_request_fails(&client(&closed_port()));
+}
+
+#[test]
+fn client_fails_on_closed_connection() {
+    assert_every_request_fails(&client(&stand_in_server(b"")));
+}
+
+#[test]
+fn client_fails_on_invalid_http() {
+    assert_every_request_fails(&client(&stand_in_server(b"NOT HTTP AT ALL\r\n\r\n")));
+}
+
+#[test]
+fn client_fails_on_server_error() {
+    let response = b"HTTP/1.1 500 Internal Server Error\r\n\
+                     Content-Length: 4\r\n\
+                     Connection: close\r\n\r\n\
+                     oops";
+    assert_every_request_fails(&client(&stand_in_server(response)));
+}
+
+#[test]
+fn client_fails_on_invalid_json() {
+    let response = b"HTTP/1.1 200 OK\r\n\
+                     Content-Type: application/json\r\n\
+                     Content-Length: 9\r\n\
+                     Connection: close\r\n\r\n\
+                     {\"id\": [}";
+    assert_every_method_fails(&client(&stand_in_server(response)));
+}
@@ -0,0 +1,197 @@
+extern crate herder;
+
+use herder::Mastodon;
+use herder::api::v1::decoding::Decoding;
+use herder::api::v1::entities::{Attachment, AttachmentType, Card, Status, Visibility};
+use herder::mastodon::NodeInstance;
+
+const BASE_URL: &str = "http://localhost:3000";
+const MY_TOKEN: &str = "MY_TOKEN";
+
+const LOCAL_ATTACHMENT_JSON: &[u8] = br#"{
+    "id": "22345792",
+    "type": "image",
+    "url": "https://files.mastodon.example/media_attachments/files/022/345/792/original/cat.png",
+    "preview_url": "https://files.mastodon.example/media_attachments/files/022/345/792/small/cat.png",
+    "remote_url": null,
+    "text_url": "https://mastodon.example/media/2N4uvkuUtPVrkZGysms",
+    "description": null
+}"#;
+
+const CARD_WITHOUT_IMAGE_JSON: &[u8] = br#"{
+    "url": "https://crates.io/crates/herder",
+    "title": "herder - crates.io",
+    "description": null
+}"#;
+
+const COMPLETE_CARD_JSON: &[u8] = br#"{
+    "url": "https://crates.io/crates/herder",
+    "title": "herder - crates.io",
+    "description": "A client for Mastodon",
+    "image": null
+}"#;
+
+const TIMELINE_JSON: &[u8] = br#"[
+    {
+        "id": "1",
+        "uri": "https://mastodon.example/users/herder/statuses/1",
+        "url": "https://mastodon.example/@herder/1",
+        "account": {
+            "id": "1",
+            "username": "herder",
+            "acct": "herder",
+            "display_name": "Herder",
+            "note": "",
+            "url": "https://mastodon.example/@herder",
+            "avatar": "https://mastodon.example/avatars/original/missing.png",
+            "header": "https://mastodon.example/headers/original/missing.png",
+            "locked": false,
+            "created_at": "2017-04-01T00:00:00.000Z",
+            "followers_count": 0,
+            "following_count": 0,
+            "statuses_count": 2
+        },
+        "in_reply_to_id": null,
+        "in_reply_to_account_id": null,
+        "reblog": null,
+        "content": "<p>First toot</p>",
+        "created_at": "2017-04-01T00:00:00.000Z",
+        "reblogs_count": 0,
+        "favourites_count": 0,
+        "reblogged": false,
+        "favourited": false,
+        "sensitive": false,
+        "spoiler_text": "",
+        "visibility": "public",
+        "media_attachments": [],
+        "mentions": [],
+        "tags": [],
+        "application": null
+    },
+    {
+        "id": "2",
+        "uri": "https://remote.example/notes/2",
+        "url": null,
+        "account": {
+            "id": "2",
+            "username": "remote",
+            "acct": "remote@remote.example",
+            "display_name": null,
+            "note": null,
+            "url": "https://remote.example/users/remote",
+            "avatar": "https://remote.example/avatar.png",
+            "header": "",
+            "locked": false,
+            "created_at": "2017-04-01T00:00:00.000Z"
+        },
+        "in_reply_to_id": null,
+        "in_reply_to_account_id": null,
+        "reblog": null,
+        "content": "<p>Second toot</p>",
+        "created_at": "2017-04-02T00:00:00.000Z",
+        "sensitive": null,
+        "spoiler_text": null,
+        "visibility": "local",
+        "media_attachments": [
+            {
+                "id": "3",
+                "type": "audio",
+                "url": "https://remote.example/media/3.ogg",
+                "remote_url": null
+            }
+        ],
+        "mentions": [],
+        "tags": []
+    }
+]"#;
+
+#[test]
+fn lenient_decoding_is_the_default() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    assert_eq!(client.decoding, Decoding::Lenient);
+    assert_eq!(client.with_decoding(Decoding::Strict).decoding, Decoding::Strict);
+}
+
+#[test]
+fn lenient_decoding_defaults_null_attachment_urls() {
+    let attachment: Attachment = Decoding::Lenient.decode(LOCAL_ATTACHMENT_JSON).unwrap();
+    assert_eq!(attachment.attachment_type, AttachmentType::Image);
+    assert_eq!(attachment.remote_url, None);
+    assert_eq!(attachment.description, None);
+}
+
+#[test]
+fn lenient_decoding_defaults_missing_card_fields() {
+    let card: Card = Decoding::Lenient.decode(CARD_WITHOUT_IMAGE_JSON).unwrap();
+    assert_eq!(card, Card {
+        url: String::from("https://crates.io/crates/herder"),
+        title: String::from("herder - crates.io"),
+        description: String::new(),
+        image: None
+    });
+}
+
+#[test]
+fn lenient_decoding_keeps_the_whole_timeline() {
+    let timeline: Vec<Status> = Decoding::Lenient.decode(TIMELINE_JSON).unwrap();
+    assert_eq!(timeline.len(), 2);
+    let status = &timeline[1];
+    assert_eq!(status.url, None);
+    assert_eq!(status.account.display_name, "");
+    assert_eq!(status.account.followers_count, 0);
+    assert!(!status.reblogged);
+    assert!(!status.sensitive);
+    assert_eq!(status.spoiler_text, "");
+    assert_eq!(status.visibility, Visibility::Other(String::from("local")));
+    assert_eq!(status.media_attachments[0].attachment_type, AttachmentType::Audio);
+    assert_eq!(status.media_attachments[0].preview_url, None);
+    assert_eq!(status.application, None);
+}
+
+#[test]
+fn strict_decoding_accepts_complete_entities() {
+    let card: Card = Decoding::Strict.decode(COMPLETE_CARD_JSON).unwrap();
+    assert_eq!(card.image, None);
+    let attachment: Attachment = Decoding::Strict.decode(LOCAL_ATTACHMENT_JSON).unwrap();
+    assert_eq!(attachment.remote_url, None);
+}
+
+#[test]
+fn strict_decoding_rejects_missing_fields() {
+    let card = br#"{"url": "https://crates.io/crates/herder", "description": "A client for Mastodon"}"#;
+    assert!(Decoding::Lenient.decode::<Card>(card).is_ok());
+    assert!(Decoding::Strict.decode::<Card>(card).is_err());
+}
+
+#[test]
+fn strict_decoding_rejects_null_defaults() {
+    assert!(Decoding::Strict.decode::<Card>(CARD_WITHOUT_IMAGE_JSON).is_err());
+}
+
+#[test]
+fn strict_decoding_rejects_unknown_fields() {
+    let card = br#"{"url": "https://crates.io/crates/herder", "title": "", "description": "", "width": 400}"#;
+    assert!(Decoding::Lenient.decode::<Card>(card).is_ok());
+    assert!(Decoding::Strict.decode::<Card>(card).is_err());
+}
+
+#[test]
+fn strict_decoding_rejects_unknown_enum_values() {
+    assert_eq!(Decoding::Strict.decode::<Visibility>(br#""direct""#).unwrap(), Visibility::Direct);
+    assert!(Decoding::Strict.decode::<Visibility>(br#""local""#).is_err());
+    assert_eq!(Decoding::Lenient.decode::<Visibility>(br#""local""#).unwrap(),
+               Visibility::Other(String::from("local")));
+}
+
+#[test]
+fn strict_decoding_rejects_the_lenient_timeline() {
+    assert!(Decoding::Strict.decode::<Vec<Status>>(TIMELINE_JSON).is_err());
+}
+
+#[test]
+fn strict_decoding_of_any_type_only_rejects_unknown_values() {
+    assert!(Decoding::Strict.decode_any::<Card>(CARD_WITHOUT_IMAGE_JSON).is_ok());
+    assert!(Decoding::Strict.decode_any::<Vec<Visibility>>(br#"["direct", "local"]"#).is_err());
+    assert_eq!(Decoding::Lenient.decode_any::<Vec<Visibility>>(br#"["direct", "local"]"#).unwrap(),
+               vec![Visibility::Direct, Visibility::Other(String::from("local"))]);
+}
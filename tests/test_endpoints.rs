@@ -3,16 +3,31 @@ extern crate hyper;
 extern crate url;
 
 use herder::Mastodon;
+use herder::api::v1::entities::NotificationType;
 use herder::api::v1::methods::{
     AccountID,
     APIEndpoint,
     APIEndpointRequest,
+    HomeTimelineQuery,
+    ListID,
+    MediaID,
+    MediaUpload,
+    NewReport,
+    NewStatus,
+    NotificationID,
+    NotificationsQuery,
+    PageQuery,
     RelationshipsQuery,
     SearchAccountsQuery,
+    SearchContentsQuery,
+    SearchType,
+    StatusID,
+    TimelineQuery,
     UserFormData
 };
 use herder::mastodon::NodeInstance;
-use hyper::Method::{Get, Patch, Post};
+use hyper::Method::{Delete, Get, Patch, Post};
+use hyper::header::{Authorization, Bearer};
 
 const BASE_URL: &str = "http://localhost:3000";
 const MY_TOKEN: &str = "MY_TOKEN";
@@ -21,7 +36,7 @@ const MY_TOKEN: &str = "MY_TOKEN";
 #[test]
 fn api_request_fetch_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::FetchAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::FetchAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345")
@@ -44,14 +59,30 @@ fn api_request_update_current_user() {
     let endpoint = APIEndpoint::UpdateCurrentUser(UserFormData::new(None, None, None, None));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Patch);
-    assert_eq!(request.uri().path(), "/api/v1/accounts/update_credentials")
+    assert_eq!(request.uri().path(), "/api/v1/accounts/update_credentials");
+    let content_type = request.headers().get_raw("Content-Type").unwrap().one().unwrap();
+    assert_eq!(content_type, b"application/x-www-form-urlencoded");
+}
+
+// APIEndpoint::UpdateCurrentUser(UserFormData), with images
+#[test]
+fn api_request_update_current_user_with_avatar() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let avatar = MediaUpload::from_bytes("avatar.png", "image/png", b"PNG".to_vec());
+    let form_data = UserFormData::new(None, None, None, None).avatar(avatar);
+    let endpoint = APIEndpoint::UpdateCurrentUser(form_data);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Patch);
+    assert_eq!(request.uri().path(), "/api/v1/accounts/update_credentials");
+    let content_type = request.headers().get_raw("Content-Type").unwrap().one().unwrap();
+    assert!(content_type.starts_with(b"multipart/form-data; boundary="));
 }
 
 // APIEndpoint::GetAccountFollowers(AccountID)
 #[test]
 fn api_request_get_account_followers() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::GetAccountFollowers(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::GetAccountFollowers(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/followers")
@@ -61,7 +92,7 @@ fn api_request_get_account_followers() {
 #[test]
 fn api_request_get_account_following() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::GetFollowing(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::GetFollowing(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/following")
@@ -71,7 +102,7 @@ fn api_request_get_account_following() {
 #[test]
 fn api_request_get_account_statuses() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::GetAccountStatuses(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::GetAccountStatuses(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/statuses")
@@ -81,7 +112,7 @@ fn api_request_get_account_statuses() {
 #[test]
 fn api_request_following_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::FollowAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::FollowAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/follow")
@@ -91,7 +122,7 @@ fn api_request_following_an_account() {
 #[test]
 fn api_request_unfollowing_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::UnfollowAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::UnfollowAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/unfollow")
@@ -101,27 +132,29 @@ fn api_request_unfollowing_an_account() {
 #[test]
 fn api_request_block_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::BlockAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::BlockAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
-    assert_eq!(request.uri().path(), "/api/v1/accounts/12345/block")
+    assert_eq!(request.uri().path(), "/api/v1/accounts/12345/block");
+    assert!(request.headers().has::<Authorization<Bearer>>())
 }
 
 // APIEndpoint::UnblockAccount(AccountID)
 #[test]
 fn api_request_unblock_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::UnblockAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::UnblockAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
-    assert_eq!(request.uri().path(), "/api/v1/accounts/12345/unblock")
+    assert_eq!(request.uri().path(), "/api/v1/accounts/12345/unblock");
+    assert!(request.headers().has::<Authorization<Bearer>>())
 }
 
 // APIEndpoint::MuteAccount(AccountID)
 #[test]
 fn api_request_mute_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::MuteAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::MuteAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/mute")
@@ -131,7 +164,7 @@ fn api_request_mute_an_account() {
 #[test]
 fn api_request_unmute_an_account() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::UnmuteAccount(AccountID { id: 12345 });
+    let endpoint = APIEndpoint::UnmuteAccount(AccountID::from(12345));
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Post);
     assert_eq!(request.uri().path(), "/api/v1/accounts/12345/unmute")
@@ -141,7 +174,7 @@ fn api_request_unmute_an_account() {
 #[test]
 fn api_request_single_account_relationship() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let query = RelationshipsQuery::Single(AccountID { id: 12345 });
+    let query = RelationshipsQuery::SingleAccount(AccountID::from(12345));
     let endpoint = APIEndpoint::GetAccountRelationships(query);
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
@@ -152,8 +185,8 @@ fn api_request_single_account_relationship() {
 #[test]
 fn api_request_many_accounts_relationship() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let accounts = vec![AccountID { id: 12345 }, AccountID { id: 67890 }];
-    let query = RelationshipsQuery::Many(accounts);
+    let accounts = vec![AccountID::from(12345), AccountID::from(67890)];
+    let query = RelationshipsQuery::MultipleAccounts(accounts);
     let endpoint = APIEndpoint::GetAccountRelationships(query);
     let request = client.build_request(endpoint).unwrap();
     assert_eq!(*request.method(), Get);
@@ -170,151 +203,406 @@ fn api_request_search_accounts() {
     assert_eq!(*request.method(), Get);
     assert_eq!(request.uri().path(), "/api/v1/accounts/search")
 }
+// APIEndpoint::FetchBlocks(PageQuery)
 #[test]
-// APIEndpoint::FetchBlocks(AccountID)
 fn api_request_fetch_blocks() {
     let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
-    let endpoint = APIEndpoint::FetchBlocks(AccountID { id: 12345 });
+    let query = PageQuery { max_id: Some(String::from("200")), since_id: None, limit: Some(20) };
+    let endpoint = APIEndpoint::FetchBlocks(query);
     let request = client.build_request(endpoint).unwrap();
-    assert_eq!(*request.method(), Post);
-    assert_eq!(request.uri().path(), "/api/v1/accounts/12345/mute")
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/blocks");
+    assert_eq!(request.uri().query(), Some("max_id=200&limit=20"))
 }
-// APIEndpoint::FetchFollowRequests(AccountID)
+// APIEndpoint::FetchFavourites(PageQuery)
 #[test]
 fn api_request_fetch_favourites() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = PageQuery { since_id: Some(String::from("100")), ..PageQuery::default() };
+    let endpoint = APIEndpoint::FetchFavourites(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/favourites");
+    assert_eq!(request.uri().query(), Some("since_id=100"))
 }
-// APIEndpoint::FetchFollowRequests(Query)
+// APIEndpoint::FetchFollowRequests(PageQuery)
 #[test]
 fn api_request_fetch_follow_requests() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchFollowRequests(PageQuery { limit: Some(20), ..PageQuery::default() });
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/follow_requests");
+    assert_eq!(request.uri().query(), Some("limit=20"))
 }
-// APIEndpoint::AuthorizeFollowRequest(FormData)
+// APIEndpoint::AuthorizeFollowRequest(AccountID)
 #[test]
 fn api_request_authorize_follow_request(){
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::AuthorizeFollowRequest(AccountID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/follow_requests/12345/authorize")
 }
-// APIEndpoint::RejectFollowRequest(FormData)
+// APIEndpoint::RejectFollowRequest(AccountID)
 #[test]
 fn api_request_reject_follow_request() {
-    unimplemented!()
-}
-// APIEndpoint::FollowRemoteUser(FormData)
-#[test]
-fn api_request_follow_remote_user() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::RejectFollowRequest(AccountID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/follow_requests/12345/reject")
 }
 // APIEndpoint::GetInstance
 #[test]
 fn api_request_get_instance() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetInstance;
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/instance")
 }
-// APIEndpoint::UploadMedia
+// APIEndpoint::GetInstanceV2
+#[test]
+fn api_request_get_instance_v2() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetInstanceV2;
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v2/instance")
+}
+// APIEndpoint::UploadMedia(MediaUpload)
 #[test]
 fn api_request_upload_media() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let media = MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec());
+    let endpoint = APIEndpoint::UploadMedia(media);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/media");
+    let content_type = request.headers().get_raw("Content-Type").unwrap().one().unwrap();
+    assert!(content_type.starts_with(b"multipart/form-data; boundary="));
 }
-// APIEndpoint::FetchMutes(Query)
+// APIEndpoint::UploadMediaV2(MediaUpload)
+#[test]
+fn api_request_upload_media_v2() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let media = MediaUpload::from_bytes("cat.png", "image/png", b"PNG".to_vec());
+    let endpoint = APIEndpoint::UploadMediaV2(media);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v2/media")
+}
+// APIEndpoint::UploadMedia(MediaUpload), without media
+#[test]
+fn api_request_upload_empty_media_is_rejected() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let media = MediaUpload::from_bytes("cat.png", "image/png", Vec::new());
+    assert!(client.build_request(APIEndpoint::UploadMedia(media)).is_err());
+}
+// APIEndpoint::GetMedia(MediaID)
+#[test]
+fn api_request_get_media() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetMedia(MediaID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/media/12345")
+}
+// APIEndpoint::FetchMutes(PageQuery)
 #[test]
 fn api_request_fetch_mutes() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchMutes(PageQuery::default());
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/mutes");
+    assert_eq!(request.uri().query(), None)
+}
+// APIEndpoint::FetchNotifications(NotificationsQuery)
+#[test]
+fn api_request_fetch_notifications_without_query() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchNotifications(NotificationsQuery::default());
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/notifications");
+    assert_eq!(request.uri().query(), None)
 }
-// APIEndpoint::FetchNotifications(Query)
 #[test]
 fn api_request_fetch_notifications() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = NotificationsQuery {
+        limit: Some(15),
+        exclude_types: vec![NotificationType::Follow, NotificationType::Favourite],
+        account_id: Some(AccountID::from(12345)),
+        ..NotificationsQuery::default()
+    };
+    let endpoint = APIEndpoint::FetchNotifications(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/notifications");
+    assert_eq!(request.uri().query(),
+               Some("limit=15&exclude_types%5B%5D=follow&exclude_types%5B%5D=favourite&account_id=12345"))
 }
 // APIEndpoint::GetNotification(NotificationID)
 #[test]
 fn api_request_get_notification() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetNotification(NotificationID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/notifications/12345")
 }
 // APIEndpoint::ClearNotifications
 #[test]
 fn api_request_clear_notifications() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::ClearNotifications;
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/notifications/clear")
+}
+// APIEndpoint::DismissNotification(NotificationID)
+#[test]
+fn api_request_dismiss_notification() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::DismissNotification(NotificationID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/notifications/dismiss")
 }
-// APIEndpoint::FetchingReports
+// APIEndpoint::FetchReports
 #[test]
 fn api_request_fetching_reports() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchReports;
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/reports")
 }
-// APIEndpoint::ReportingUser(UserReport)
+// APIEndpoint::ReportUser(NewReport)
 #[test]
 fn api_request_reporting_user() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let report = NewReport::new(AccountID::from(12345)).comment("spam");
+    let endpoint = APIEndpoint::ReportUser(report);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/reports")
 }
-// APIEndpoint::SearchContent(ContentQuery)
+// APIEndpoint::ReportUser(NewReport), with an invalid category
+#[test]
+fn api_request_reporting_user_with_invalid_category_is_rejected() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let report = NewReport::new(AccountID::from(12345)).category("rude");
+    assert!(client.build_request(APIEndpoint::ReportUser(report)).is_err());
+}
+// APIEndpoint::SearchContent(SearchContentsQuery)
 #[test]
 fn api_request_search_content() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = SearchContentsQuery {
+        q: String::from("herder"),
+        resolve: Some(true),
+        ..SearchContentsQuery::default()
+    };
+    let endpoint = APIEndpoint::SearchContent(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/search");
+    assert_eq!(request.uri().query(), Some("q=herder&resolve=true"))
+}
+// APIEndpoint::SearchContentV2(SearchContentsQuery)
+#[test]
+fn api_request_search_content_v2() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = SearchContentsQuery {
+        q: String::from("#rust lang"),
+        search_type: Some(SearchType::Statuses),
+        following: Some(false),
+        account_id: Some(AccountID::from(12345)),
+        offset: Some(20),
+        limit: Some(10),
+        ..SearchContentsQuery::default()
+    };
+    let endpoint = APIEndpoint::SearchContentV2(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v2/search");
+    assert_eq!(request.uri().query(),
+               Some("q=%23rust+lang&type=statuses&following=false&account_id=12345&offset=20&limit=10"))
 }
 // APIEndpoint::FetchStatus(StatusID)
 #[test]
 fn api_request_fetch_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345")
+}
+
+#[test]
+fn api_request_fetch_status_escapes_the_id() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FetchStatus(StatusID::from("1/../../admin?x=1"));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(request.uri().path(), "/api/v1/statuses/1%2F..%2F..%2Fadmin%3Fx=1");
+    assert_eq!(request.uri().query(), None);
+}
+
+#[test]
+fn api_request_fetch_status_with_empty_id_is_rejected() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    assert!(client.build_request(APIEndpoint::FetchStatus(StatusID::from(""))).is_err());
+    assert!(client.build_request(APIEndpoint::GetStatusContext(StatusID::new(String::new()))).is_err());
 }
 // APIEndpoint::GetStatusContext(StatusID)
 #[test]
 fn api_request_get_status_context() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetStatusContext(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/context")
 }
 // APIEndpoint::GetStatusCard(StatusID)
 #[test]
 fn api_request_get_status_card() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::GetStatusCard(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/card")
 }
 // APIEndpoint::RebloggedBy(StatusID)
 #[test]
 fn api_request_reblogged_by() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::RebloggedBy(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/reblogged_by")
 }
 // APIEndpoint::FavouritedBy(StatusID)
 #[test]
 fn api_request_favourited_by() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FavouritedBy(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/favourited_by")
 }
-// APIEndpoint::PostStatus(PostFormData)
+// APIEndpoint::PostStatus(NewStatus)
 #[test]
 fn api_request_post_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::PostStatus(NewStatus::new("My tooted toot!"));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/statuses")
+}
+// APIEndpoint::PostStatus(NewStatus)
+#[test]
+fn api_request_post_empty_status_is_rejected() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::PostStatus(NewStatus::new(""));
+    assert!(client.build_request(endpoint).is_err());
 }
 // APIEndpoint::DeleteStatus(StatusID)
 #[test]
 fn api_request_delete_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::DeleteStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Delete);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345")
 }
 // APIEndpoint::ReblogStatus(StatusID)
 #[test]
 fn api_request_reblog_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::ReblogStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/reblog")
 }
 // APIEndpoint::UnreblogStatus(StatusID)
 #[test]
 fn api_request_unreblog_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::UnreblogStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/unreblog")
 }
 // APIEndpoint::FavouriteStatus(StatusID)
 #[test]
 fn api_request_favourite_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::FavouriteStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/favourite")
 }
 // APIEndpoint::UnfavouriteStatus(StatusID)
 #[test]
 fn api_request_unfavourite_status() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::UnfavouriteStatus(StatusID::from(12345));
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Post);
+    assert_eq!(request.uri().path(), "/api/v1/statuses/12345/unfavourite")
 }
-// APIEndpoint::PublicTimeLine(PublicQuery)
+// APIEndpoint::HomeTimeline(HomeTimelineQuery)
 #[test]
 fn api_request_home_timeline() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = HomeTimelineQuery { max_id: Some(String::from("200")), since_id: None, limit: Some(20) };
+    let endpoint = APIEndpoint::HomeTimeline(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/timelines/home");
+    assert_eq!(request.uri().query(), Some("max_id=200&limit=20"))
 }
-// APIEndpoint::PublicTimeLine(PublicQuery)
+// APIEndpoint::PublicTimeline(TimelineQuery)
 #[test]
 fn api_request_public_timeline() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let query = TimelineQuery { local: Some(true), ..TimelineQuery::default() };
+    let endpoint = APIEndpoint::PublicTimeline(query);
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/timelines/public");
+    assert_eq!(request.uri().query(), Some("local=true"))
 }
-// APIEndpoint::TagTimeLine(Tag, TagQuery)
+// APIEndpoint::TagTimeline(String, TimelineQuery)
 #[test]
 fn api_request_tag_timeline() {
-    unimplemented!()
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::TagTimeline(String::from("#rustlang"), TimelineQuery::default());
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/timelines/tag/rustlang");
+    assert_eq!(request.uri().query(), None)
+}
+// APIEndpoint::ListTimeline(ListID, HomeTimelineQuery)
+#[test]
+fn api_request_list_timeline() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::ListTimeline(ListID::from(12345), HomeTimelineQuery::default());
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/timelines/list/12345");
+    assert_eq!(request.uri().query(), None)
+}
+// APIEndpoint::VerifyAppCredentials
+#[test]
+fn api_request_verify_app_credentials() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let endpoint = APIEndpoint::VerifyAppCredentials;
+    let request = client.build_request(endpoint).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/apps/verify_credentials")
 }
@@ -0,0 +1,108 @@
+extern crate herder;
+extern crate hyper;
+
+use herder::errors::{Error, ErrorKind};
+use hyper::StatusCode;
+use hyper::header::Headers;
+use std::time::Duration;
+
+#[test]
+fn unauthorized_response_error() {
+    let body = br#"{"error": "invalid_token", "error_description": "The access token is invalid"}"#;
+    match *Error::from_response(StatusCode::Unauthorized, &Headers::new(), body).kind() {
+        ErrorKind::Unauthorized(ref error, ref description) => {
+            assert_eq!(error, "invalid_token");
+            assert_eq!(description, &Some(String::from("The access token is invalid")));
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn not_found_response_error() {
+    let body = br#"{"error": "Record not found"}"#;
+    match *Error::from_response(StatusCode::NotFound, &Headers::new(), body).kind() {
+        ErrorKind::NotFound(ref error, None) => assert_eq!(error, "Record not found"),
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn validation_response_error() {
+    let body = br#"{"error": "Validation failed: Text can't be blank"}"#;
+    match *Error::from_response(StatusCode::UnprocessableEntity, &Headers::new(), body).kind() {
+        ErrorKind::Validation(ref error, None) => assert_eq!(error, "Validation failed: Text can't be blank"),
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn rate_limited_response_error() {
+    let body = br#"{"error": "Throttled"}"#;
+    let mut headers = Headers::new();
+    headers.set_raw("Retry-After", "30");
+    match *Error::from_response(StatusCode::TooManyRequests, &headers, body).kind() {
+        ErrorKind::RateLimited(ref error, None, retry_after) => {
+            assert_eq!(error, "Throttled");
+            assert_eq!(retry_after, Some(Duration::from_secs(30)));
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn rate_limited_response_error_with_past_retry_date() {
+    let body = br#"{"error": "Throttled"}"#;
+    let mut headers = Headers::new();
+    headers.set_raw("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT");
+    match *Error::from_response(StatusCode::TooManyRequests, &headers, body).kind() {
+        ErrorKind::RateLimited(_, _, retry_after) => assert_eq!(retry_after, Some(Duration::from_secs(0))),
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn rate_limited_response_error_without_retry_after() {
+    match *Error::from_response(StatusCode::TooManyRequests, &Headers::new(), b"").kind() {
+        ErrorKind::RateLimited(ref error, None, None) => assert_eq!(error, "Too Many Requests"),
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn other_api_response_error() {
+    let body = br#"{"error": "invalid_grant", "error_description": "The grant is invalid"}"#;
+    match *Error::from_response(StatusCode::BadRequest, &Headers::new(), body).kind() {
+        ErrorKind::Api(status, ref error, ref description) => {
+            assert_eq!(status, 400);
+            assert_eq!(error, "invalid_grant");
+            assert_eq!(description, &Some(String::from("The grant is invalid")));
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn non_json_response_error() {
+    match *Error::from_response(StatusCode::BadGateway, &Headers::new(), b"").kind() {
+        ErrorKind::Api(status, ref error, None) => {
+            assert_eq!(status, 502);
+            assert_eq!(error, "Bad Gateway");
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn html_response_error_keeps_a_snippet() {
+    let body = format!("<html><body><h1>500 Internal Server Error</h1>{}</body></html>", "x".repeat(4096));
+    match *Error::from_response(StatusCode::InternalServerError, &Headers::new(), body.as_bytes()).kind() {
+        ErrorKind::Api(status, ref error, Some(ref description)) => {
+            assert_eq!(status, 500);
+            assert_eq!(error, "Internal Server Error");
+            assert!(description.starts_with("<html><body><h1>500 Internal Server Error</h1>"));
+            assert!(description.len() < 256);
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
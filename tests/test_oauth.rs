@@ -1,7 +1,20 @@
 extern crate herder;
 extern crate serde_json;
+extern crate url;
 
-use herder::api::oauth::OAuthApp;
+use herder::Mastodon;
+use herder::api::oauth::{AccessToken, AuthorizationType, OAuthApp};
+use herder::mastodon::NodeInstance;
+use url::Url;
+
+fn mock_oauth_app() -> OAuthApp {
+    OAuthApp {
+        id: Some(1234),
+        client_id: String::from("CID"),
+        client_secret: String::from("CSEC"),
+        redirect_uri: String::from("urn:ietf:wg:oauth:2.0:oob")
+    }
+}
 
 #[test]
 fn oauthapp_from_json() {
@@ -19,3 +32,72 @@ fn oauthapp_from_json() {
                         redirect_uri: String::from("MYURI")
     });
 }
+
+#[test]
+fn access_token_from_json() {
+    let ojson = r#"{
+                    "access_token": "MYTOKEN",
+                    "token_type": "bearer",
+                    "scope": "read write",
+                    "created_at": 1492000000
+                 }"#;
+    let token: AccessToken = serde_json::from_str(ojson).unwrap();
+    assert_eq!(token, AccessToken {
+        access_token: String::from("MYTOKEN"),
+        token_type: String::from("bearer"),
+        scope: String::from("read write"),
+        created_at: 1492000000
+    });
+}
+
+#[test]
+fn oauthapp_authorize_url() {
+    let mastodon = Mastodon::new("https://mastodon.example.com").unwrap();
+    let url = mastodon.authorize_url(&mock_oauth_app(), "read write").unwrap();
+    assert_eq!(url, Url::parse("https://mastodon.example.com/oauth/authorize?client_id=CID\
+                                &redirect_uri=urn%3Aietf%3Awg%3Aoauth%3A2.0%3Aoob\
+                                &response_type=code&scope=read+write").unwrap());
+}
+
+#[test]
+fn client_credentials_grant_form() {
+    let form = AuthorizationType::ClientCredentials.form_encode(&mock_oauth_app(), "read").unwrap();
+    assert_eq!(form, "client_id=CID&client_secret=CSEC&grant_type=client_credentials&scope=read");
+}
+
+#[test]
+fn password_grant_form() {
+    let grant = AuthorizationType::Password {
+        user: String::from("jane@example.com"),
+        pass: String::from("s3cr3t")
+    };
+    let form = grant.form_encode(&mock_oauth_app(), "read").unwrap();
+    assert_eq!(form, "client_id=CID&client_secret=CSEC&grant_type=password\
+                      &username=jane%40example.com&password=s3cr3t&scope=read");
+}
+
+#[test]
+fn authorization_code_grant_form() {
+    let grant = AuthorizationType::RequestAuthorization { code: String::from("MYCODE") };
+    let form = grant.form_encode(&mock_oauth_app(), "read").unwrap();
+    assert_eq!(form, "client_id=CID&client_secret=CSEC&grant_type=authorization_code\
+                      &redirect_uri=urn%3Aietf%3Awg%3Aoauth%3A2.0%3Aoob&code=MYCODE&scope=read");
+}
+
+#[test]
+fn client_from_access_token() {
+    let token = AccessToken {
+        access_token: String::from("MYTOKEN"),
+        token_type: String::from("bearer"),
+        scope: String::from("read"),
+        created_at: 1492000000
+    };
+    let client = Mastodon::new("https://mastodon.example.com").unwrap().client(&token).unwrap();
+    assert_eq!(client.token.token, "MYTOKEN");
+}
+
+#[test]
+fn revoke_token_form() {
+    let form = mock_oauth_app().revoke_form_encode("MYTOKEN").unwrap();
+    assert_eq!(form, "client_id=CID&client_secret=CSEC&token=MYTOKEN");
+}
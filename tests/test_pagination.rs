@@ -0,0 +1,97 @@
+extern crate herder;
+extern crate hyper;
+extern crate url;
+
+use herder::Mastodon;
+use herder::api::v1::decoding::Decoding;
+use herder::api::v1::entities::Account;
+use herder::api::v1::methods::{APIEndpoint, APIEndpointRequest, PageQuery};
+use herder::api::v1::pagination::Page;
+use herder::mastodon::NodeInstance;
+use hyper::Headers;
+use hyper::Method::Get;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use url::Url;
+
+const BASE_URL: &str = "http://localhost:3000";
+const MY_TOKEN: &str = "MY_TOKEN";
+
+#[test]
+fn page_cursors_from_link_header() {
+    let mut headers = Headers::new();
+    headers.set_raw("Link", String::from(
+        "<http://localhost:3000/api/v1/accounts/1/followers?max_id=7>; rel=\"next\", \
+         <http://localhost:3000/api/v1/accounts/1/followers?since_id=9>; rel=\"prev\""));
+    let page = Page::new(vec![1, 2, 3], &headers);
+    assert_eq!(page.items, vec![1, 2, 3]);
+    assert_eq!(page.next,
+               Some(Url::parse("http://localhost:3000/api/v1/accounts/1/followers?max_id=7").unwrap()));
+    assert_eq!(page.prev,
+               Some(Url::parse("http://localhost:3000/api/v1/accounts/1/followers?since_id=9").unwrap()));
+}
+
+#[test]
+fn page_without_link_header() {
+    let page: Page<i32> = Page::new(Vec::new(), &Headers::new());
+    assert_eq!(page.next, None);
+    assert_eq!(page.prev, None);
+}
+
+// APIEndpoint::FetchPage(Url)
+#[test]
+fn api_request_fetch_page() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let url = Url::parse("http://localhost:3000/api/v1/accounts/1/followers?max_id=7").unwrap();
+    let request = client.build_request(APIEndpoint::FetchPage(url)).unwrap();
+    assert_eq!(*request.method(), Get);
+    assert_eq!(request.uri().path(), "/api/v1/accounts/1/followers");
+    assert_eq!(request.uri().query(), Some("max_id=7"))
+}
+
+// APIEndpoint::FetchPage(Url)
+#[test]
+fn api_request_fetch_page_from_another_host_is_rejected() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let url = Url::parse("https://example.com/api/v1/accounts/1/followers?max_id=7").unwrap();
+    assert!(client.build_request(APIEndpoint::FetchPage(url)).is_err());
+}
+
+const BLOCKED_ACCOUNT_JSON: &str = r#"{"id": "1", "username": "spammer", "acct": "spammer", "display_name": "", "note": "", "url": "https://mastodon.example/@spammer", "avatar": "", "avatar_static": "", "header": "", "locked": false, "emojis": [], "fields": [], "bot": false, "group": false, "created_at": "2017-04-01T00:00:00Z", "followers_count": 0, "following_count": 0, "statuses_count": 0}"#;
+
+/// Serves the JSON `body` as the response to every request.
+fn stand_in_server(body: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                            Content-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break
+            };
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn strict_pages_check_the_fields_of_each_entity() {
+    let url = stand_in_server(format!("[{}]", BLOCKED_ACCOUNT_JSON));
+    let client = Mastodon::new(&url).unwrap().client(MY_TOKEN).unwrap().with_decoding(Decoding::Strict);
+    let mut blocks = client.iter_blocks(Some(1));
+    assert_eq!(blocks.next().unwrap().unwrap().acct, "spammer");
+
+    let unknown_field = BLOCKED_ACCOUNT_JSON.replace("\"bot\"", "\"noindex\": true, \"bot\"");
+    let url = stand_in_server(format!("[{}]", unknown_field));
+    let client = Mastodon::new(&url).unwrap().client(MY_TOKEN).unwrap();
+    assert!(client.iter_blocks(Some(1)).next().unwrap().is_ok());
+    let client = client.with_decoding(Decoding::Strict);
+    assert!(client.iter_blocks(Some(1)).next().unwrap().is_err());
+    assert!(client.fetch_entity_page::<Account>(APIEndpoint::FetchBlocks(PageQuery::default())).is_err());
+}
@@ -0,0 +1,257 @@
+extern crate futures;
+extern crate herder;
+extern crate hyper;
+extern crate tokio_core;
+extern crate url;
+
+use futures::Stream;
+use herder::Mastodon;
+use herder::api::v1::decoding::Decoding;
+use herder::api::v1::entities::Visibility;
+use herder::api::v1::methods::{APIEndpoint, APIEndpointRequest, ListID, StatusID};
+use herder::api::v1::streaming::{streaming_base_url, Event, EventParser, StreamingTimeline};
+use herder::mastodon::NodeInstance;
+use hyper::Method::Get;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use tokio_core::reactor::Core;
+use url::Url;
+
+const BASE_URL: &str = "http://localhost:3000";
+const MY_TOKEN: &str = "MY_TOKEN";
+
+const STATUS_JSON: &str = r#"{"id": 1234, "uri": "MYURI", "url": "MYURL", "account": {"id": 1234, "username": "MYUSERNAME", "acct": "MYUSERNAME@MYREMOTEDOMAIN", "display_name": "jane_sanchez", "note": "A self-described person", "url": "MYRUL", "avatar": "MYURL.png", "header": "MYHEADER.png", "locked": false, "created_at": "2000-01-01T00:00:00Z", "followers_count": 1234, "following_count": 1234, "statuses_count": 1234}, "in_reply_to_id": null, "in_reply_to_account_id": null, "reblog": null, "content": "My tooted toot!", "created_at": "2000-01-01T00:00:00Z", "reblogs_count": 0, "favourites_count": 0, "reblogged": false, "favourited": false, "sensitive": false, "spoiler_text": "", "visibility": "public", "media_attachments": [], "mentions": [], "tags": [], "application": {"name": "MYNAME", "website": null}}"#;
+
+fn streaming_request_path(timeline: StreamingTimeline) -> (String, Option<String>) {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    let request = client.build_request(APIEndpoint::Streaming(timeline)).unwrap();
+    assert_eq!(*request.method(), Get);
+    (request.uri().path().to_owned(), request.uri().query().map(|q| q.to_owned()))
+}
+
+#[test]
+fn api_request_streaming_user() {
+    assert_eq!(streaming_request_path(StreamingTimeline::User),
+               (String::from("/api/v1/streaming/user"), None));
+}
+
+#[test]
+fn api_request_streaming_public() {
+    assert_eq!(streaming_request_path(StreamingTimeline::Public),
+               (String::from("/api/v1/streaming/public"), None));
+}
+
+#[test]
+fn api_request_streaming_public_local() {
+    assert_eq!(streaming_request_path(StreamingTimeline::PublicLocal),
+               (String::from("/api/v1/streaming/public/local"), None));
+}
+
+#[test]
+fn api_request_streaming_hashtag() {
+    assert_eq!(streaming_request_path(StreamingTimeline::Hashtag(String::from("#rustlang"))),
+               (String::from("/api/v1/streaming/hashtag"), Some(String::from("tag=rustlang"))));
+}
+
+#[test]
+fn api_request_streaming_list() {
+    assert_eq!(streaming_request_path(StreamingTimeline::List(ListID::from(12))),
+               (String::from("/api/v1/streaming/list"), Some(String::from("list=12"))));
+}
+
+#[test]
+fn api_request_streaming_on_streaming_host() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap()
+        .with_streaming_url(Url::parse("https://streaming.mastodon.example").unwrap());
+    let request = client.build_request(APIEndpoint::Streaming(StreamingTimeline::User)).unwrap();
+    assert_eq!(request.uri().host(), Some("streaming.mastodon.example"));
+    assert_eq!(request.uri().path(), "/api/v1/streaming/user");
+}
+
+#[test]
+fn streaming_base_url_from_websocket_url() {
+    assert_eq!(streaming_base_url("wss://streaming.mastodon.example").unwrap(),
+               Url::parse("https://streaming.mastodon.example").unwrap());
+    assert_eq!(streaming_base_url("ws://localhost:4000").unwrap(),
+               Url::parse("http://localhost:4000").unwrap());
+    assert_eq!(streaming_base_url("https://mastodon.example").unwrap(),
+               Url::parse("https://mastodon.example").unwrap());
+    assert!(streaming_base_url("streaming").is_err());
+}
+
+/// Starts a local stand-in for a host, answering every request with `response`.
+fn stand_in_server(response: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break
+            };
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(&response);
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn streaming_host() -> String {
+    stand_in_server(b"HTTP/1.1 200 OK\r\n\
+                      Content-Type: text/event-stream\r\n\
+                      Connection: close\r\n\r\n\
+                      event: delete\n\
+                      data: 1234\n\n".to_vec())
+}
+
+fn first_event(base_url: &str, streaming_url: Option<&str>) -> Option<Event> {
+    let mut core = Core::new().unwrap();
+    let mut client = Mastodon::new(base_url).unwrap().client(MY_TOKEN).unwrap();
+    if let Some(streaming_url) = streaming_url {
+        client = client.with_streaming_url(Url::parse(streaming_url).unwrap());
+    }
+    let stream = herder::AsyncClient::new(client, &core.handle()).stream(StreamingTimeline::User).unwrap();
+    let (event, _) = core.run(stream.into_future()).map_err(|(e, _)| e).unwrap();
+    event.map(|event| event.unwrap())
+}
+
+#[test]
+fn stream_from_streaming_host() {
+    let api_host = stand_in_server(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec());
+    let streaming = streaming_host();
+    assert_eq!(first_event(&api_host, Some(&streaming)), Some(Event::Delete(StatusID::from(1234))));
+}
+
+#[test]
+fn stream_follows_redirect_to_streaming_host() {
+    let streaming = streaming_host();
+    let redirect = format!("HTTP/1.1 302 Found\r\n\
+                            Location: {}/api/v1/streaming/user\r\n\
+                            Content-Length: 0\r\n\r\n", streaming);
+    let api_host = stand_in_server(redirect.into_bytes());
+    assert_eq!(first_event(&api_host, None), Some(Event::Delete(StatusID::from(1234))));
+}
+
+#[test]
+fn stream_does_not_send_the_token_on_a_cross_origin_redirect() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let streaming = format!("http://{}", listener.local_addr().unwrap());
+    let (requests, received) = mpsc::channel();
+    thread::spawn(move || {
+        let mut stream = listener.accept().unwrap().0;
+        let mut buf = [0; 4096];
+        let n = stream.read(&mut buf).unwrap();
+        requests.send(String::from_utf8_lossy(&buf[..n]).into_owned()).unwrap();
+        let _ = stream.write_all(b"HTTP/1.1 200 OK\r\n\
+                                   Content-Type: text/event-stream\r\n\
+                                   Connection: close\r\n\r\n\
+                                   event: delete\n\
+                                   data: 1234\n\n");
+    });
+    let redirect = format!("HTTP/1.1 302 Found\r\n\
+                            Location: {}/api/v1/streaming/user\r\n\
+                            Content-Length: 0\r\n\r\n", streaming);
+    let api_host = stand_in_server(redirect.into_bytes());
+    assert_eq!(first_event(&api_host, None), Some(Event::Delete(StatusID::from(1234))));
+    let request = received.recv().unwrap().to_lowercase();
+    assert!(request.starts_with("get /api/v1/streaming/user"));
+    assert!(!request.contains("authorization"));
+}
+
+#[test]
+fn stream_goes_on_after_an_invalid_event() {
+    let streaming = stand_in_server(b"HTTP/1.1 200 OK\r\n\
+                                      Content-Type: text/event-stream\r\n\
+                                      Connection: close\r\n\r\n\
+                                      event: update\n\
+                                      data: {\"id\": \n\n\
+                                      event: delete\n\
+                                      data: 1234\n\n".to_vec());
+    let mut core = Core::new().unwrap();
+    let client = Mastodon::new(&streaming).unwrap().client(MY_TOKEN).unwrap();
+    let stream = herder::AsyncClient::new(client, &core.handle()).stream(StreamingTimeline::User).unwrap();
+    let mut events = Vec::new();
+    core.run(stream.take(2).for_each(|event| {
+        events.push(event);
+        Ok(())
+    })).unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events[0].is_err());
+    assert_eq!(events[1].as_ref().unwrap(), &Event::Delete(StatusID::from(1234)));
+}
+
+#[test]
+fn stream_fails_when_the_request_is_rejected() {
+    let api_host = stand_in_server(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n".to_vec());
+    let mut core = Core::new().unwrap();
+    let client = Mastodon::new(&api_host).unwrap().client(MY_TOKEN).unwrap();
+    let stream = herder::AsyncClient::new(client, &core.handle()).stream(StreamingTimeline::User).unwrap();
+    assert!(core.run(stream.for_each(|_| Ok(()))).is_err());
+}
+
+#[test]
+fn parse_update_event() {
+    let mut parser = EventParser::default();
+    let events = parser.feed(format!("event: update\ndata: {}\n\n", STATUS_JSON).as_bytes());
+    assert_eq!(events.len(), 1);
+    match events[0] {
+        Ok(Event::Update(ref status)) => assert_eq!(status.status_id, StatusID::from(1234)),
+        ref event => panic!("unexpected event: {:?}", event)
+    }
+}
+
+#[test]
+fn parse_delete_event() {
+    let mut parser = EventParser::default();
+    let events = parser.feed(b"event: delete\ndata: 1234\n\n");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].as_ref().unwrap(), &Event::Delete(StatusID::from(1234)));
+}
+
+#[test]
+fn parse_filters_changed_event() {
+    let mut parser = EventParser::default();
+    let events = parser.feed(b"event: filters_changed\r\ndata: undefined\r\n\r\n");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].as_ref().unwrap(), &Event::FiltersChanged);
+}
+
+#[test]
+fn parse_events_split_across_chunks() {
+    let mut parser = EventParser::default();
+    assert!(parser.feed(b"event: del").is_empty());
+    assert!(parser.feed(b"ete\ndata: 12").is_empty());
+    let events = parser.feed(b"34\n\nevent: delete\ndata: 5678\n\n");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].as_ref().unwrap(), &Event::Delete(StatusID::from(1234)));
+    assert_eq!(events[1].as_ref().unwrap(), &Event::Delete(StatusID::from(5678)));
+}
+
+#[test]
+fn heartbeats_and_unknown_events_are_skipped() {
+    let mut parser = EventParser::default();
+    assert!(parser.feed(b":thump\n\n:thump\n\nevent: unknown\ndata: {}\n\n").is_empty());
+}
+
+#[test]
+fn invalid_event_data_is_an_error() {
+    let mut parser = EventParser::default();
+    let events = parser.feed(b"event: update\ndata: {\"id\": \n\nevent: delete\ndata: 1\n\n");
+    assert_eq!(events.len(), 2);
+    assert!(events[0].is_err());
+    assert_eq!(events[1].as_ref().unwrap(), &Event::Delete(StatusID::from(1)));
+}
+
+#[test]
+fn update_events_are_decoded_with_the_parser_decoding() {
+    let update = format!("event: update\ndata: {}\n\n", STATUS_JSON.replace("\"public\"", "\"local\""));
+    let events = EventParser::new(Decoding::Lenient).feed(update.as_bytes());
+    match events[0] {
+        Ok(Event::Update(ref status)) => assert_eq!(status.visibility, Visibility::Other(String::from("local"))),
+        ref event => panic!("unexpected event: {:?}", event)
+    }
+    assert!(EventParser::new(Decoding::Strict).feed(update.as_bytes())[0].is_err());
+}
@@ -0,0 +1,145 @@
+extern crate herder;
+extern crate hyper;
+
+use herder::Mastodon;
+use herder::api::v1::methods::{APIEndpoint, APIEndpointRequest, SearchContentsQuery};
+use herder::errors::ErrorKind;
+use herder::mastodon::NodeInstance;
+use herder::version::{Capability, Version};
+use hyper::header::Authorization;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::str::FromStr;
+use std::thread;
+
+const BASE_URL: &str = "http://localhost:3000";
+const MY_TOKEN: &str = "MY_TOKEN";
+
+#[test]
+fn parse_plain_version() {
+    assert_eq!(Version::from_str("3.5.3").unwrap(), Version {
+        major: 3,
+        minor: 5,
+        patch: 3,
+        pre: None,
+        build: None,
+        compatible: None
+    });
+}
+
+#[test]
+fn parse_release_candidate_version() {
+    let version = Version::from_str("4.0.0rc1").unwrap();
+    assert_eq!((version.major, version.minor, version.patch), (4, 0, 0));
+    assert_eq!(version.pre, Some(String::from("rc1")));
+    assert_eq!(version.to_string(), "4.0.0rc1");
+}
+
+#[test]
+fn parse_fork_version() {
+    let version = Version::from_str("4.0.2+glitch").unwrap();
+    assert_eq!((version.major, version.minor, version.patch), (4, 0, 2));
+    assert_eq!(version.build, Some(String::from("glitch")));
+    assert_eq!(version.to_string(), "4.0.2+glitch");
+}
+
+#[test]
+fn parse_compatible_version() {
+    let version = Version::from_str("2.7.2 (compatible; Pleroma 2.4.0)").unwrap();
+    assert_eq!((version.major, version.minor, version.patch), (2, 7, 2));
+    assert_eq!(version.compatible, Some(String::from("Pleroma 2.4.0")));
+    assert_eq!(version.to_string(), "2.7.2 (compatible; Pleroma 2.4.0)");
+}
+
+#[test]
+fn parse_version_without_patch() {
+    let version = Version::from_str("1.4").unwrap();
+    assert_eq!((version.major, version.minor, version.patch), (1, 4, 0));
+}
+
+#[test]
+fn parse_invalid_versions() {
+    assert!(Version::from_str("").is_err());
+    assert!(Version::from_str("4").is_err());
+    assert!(Version::from_str("four.0.0").is_err());
+}
+
+#[test]
+fn version_capabilities() {
+    let version = Version::from_str("3.1.2").unwrap();
+    assert!(version.supports(Capability::SearchV2));
+    assert!(version.supports(Capability::Polls));
+    assert!(version.supports(Capability::Bookmarks));
+    assert!(!version.supports(Capability::MediaV2));
+    assert!(!version.supports(Capability::StatusEdits));
+    assert!(!version.supports(Capability::InstanceV2));
+}
+
+#[test]
+fn version_requires_capability() {
+    let version = Version::from_str("2.4.0").unwrap();
+    assert!(version.require(Capability::Polls).is_err());
+    match *version.require(Capability::SearchV2).unwrap_err().kind() {
+        ErrorKind::Unsupported(ref capability, ref version) => {
+            assert_eq!(capability, "v2 search");
+            assert_eq!(version, "2.4.0");
+        },
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+}
+
+#[test]
+fn client_fails_fast_on_unsupported_endpoint() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap()
+        .with_version(Version::from_str("2.4.0").unwrap());
+    let query = SearchContentsQuery { q: String::from("herder"), ..SearchContentsQuery::default() };
+    match *client.build_request(APIEndpoint::SearchContentV2(query)).unwrap_err().kind() {
+        ErrorKind::Unsupported(..) => {},
+        ref kind => panic!("unexpected error kind: {:?}", kind)
+    }
+    assert!(client.build_request(APIEndpoint::GetInstance).is_ok());
+}
+
+#[test]
+fn client_without_version_supports_everything() {
+    let client = Mastodon::new(BASE_URL).unwrap().client(MY_TOKEN).unwrap();
+    assert!(client.supports(Capability::InstanceV2));
+    assert!(client.build_request(APIEndpoint::GetInstanceV2).is_ok());
+}
+
+/// Starts a local stand-in for a Mastodon node, answering every request with `response`.
+fn stand_in_server(response: &'static [u8]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => break
+            };
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(response);
+        }
+    });
+    format!("http://{}", addr)
+}
+
+const INSTANCE_RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\n\
+                                   Content-Type: application/json\r\n\
+                                   Content-Length: 106\r\n\
+                                   Connection: close\r\n\r\n\
+                                   {\"uri\": \"localhost\", \"title\": \"Herder\", \"description\": \"\", \
+                                   \"email\": \"admin@localhost\", \"version\": \"3.5.3\"}";
+
+#[test]
+fn instance_version_is_fetched_without_token() {
+    let client = Mastodon::new(BASE_URL).unwrap().client("").unwrap();
+    let request = client.build_request(APIEndpoint::GetInstance).unwrap();
+    assert!(request.headers().get::<Authorization<hyper::header::Bearer>>().is_none());
+
+    let mastodon = Mastodon::new(&stand_in_server(INSTANCE_RESPONSE)).unwrap();
+    assert_eq!(mastodon.version().unwrap(), Version::from_str("3.5.3").unwrap());
+    let client = mastodon.versioned_client(MY_TOKEN).unwrap();
+    assert_eq!(client.version, Some(Version::from_str("3.5.3").unwrap()));
+}
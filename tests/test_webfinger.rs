@@ -0,0 +1,86 @@
+extern crate herder;
+extern crate serde_json;
+
+use herder::webfinger::{Acct, Jrd, Link};
+use std::str::FromStr;
+
+fn mock_acct() -> Acct {
+    Acct {
+        username: String::from("gargron"),
+        domain: String::from("mastodon.social")
+    }
+}
+
+#[test]
+fn acct_from_str() {
+    assert_eq!(Acct::from_str("gargron@mastodon.social").unwrap(), mock_acct());
+    assert_eq!(Acct::from_str("@gargron@mastodon.social").unwrap(), mock_acct());
+    assert_eq!(Acct::from_str("acct:gargron@mastodon.social").unwrap(), mock_acct());
+}
+
+#[test]
+fn acct_from_str_rejects_invalid_handles() {
+    assert!(Acct::from_str("gargron").is_err());
+    assert!(Acct::from_str("@gargron").is_err());
+    assert!(Acct::from_str("gargron@").is_err());
+    assert!(Acct::from_str("gargron@mastodon.social@example.com").is_err());
+    assert!(Acct::from_str("gargron@mastodon.social/path").is_err());
+}
+
+#[test]
+fn acct_webfinger_url() {
+    let url = mock_acct().webfinger_url().unwrap();
+    assert_eq!(url.as_str(),
+               "https://mastodon.social/.well-known/webfinger?resource=acct%3Agargron%40mastodon.social");
+}
+
+#[test]
+fn acct_matches_remote_and_local_accounts() {
+    let acct = mock_acct();
+    assert!(acct.matches("Gargron@mastodon.social", "example.com"));
+    assert!(acct.matches("Gargron", "mastodon.social"));
+    assert!(!acct.matches("gargron", "example.com"));
+    assert!(!acct.matches("someone@mastodon.social", "example.com"));
+}
+
+#[test]
+fn jrd_deserialize_from_json() {
+    let ojson = r#"{
+                    "subject": "acct:Gargron@mastodon.social",
+                    "aliases": ["https://mastodon.social/@Gargron"],
+                    "links": [
+                        {
+                            "rel": "http://webfinger.net/rel/profile-page",
+                            "type": "text/html",
+                            "href": "https://mastodon.social/@Gargron"
+                        },
+                        {
+                            "rel": "self",
+                            "type": "application/activity+json",
+                            "href": "https://mastodon.social/users/Gargron"
+                        },
+                        {
+                            "rel": "http://ostatus.org/schema/1.0/subscribe",
+                            "template": "https://mastodon.social/authorize_interaction?uri={uri}"
+                        }
+                    ]
+                 }"#;
+    let jrd: Jrd = serde_json::from_str(ojson).unwrap();
+    assert_eq!(jrd.links[2], Link {
+        rel: String::from("http://ostatus.org/schema/1.0/subscribe"),
+        link_type: None,
+        href: None,
+        template: Some(String::from("https://mastodon.social/authorize_interaction?uri={uri}"))
+    });
+    assert_eq!(jrd.acct(), Some(Acct { username: String::from("Gargron"), domain: String::from("mastodon.social") }));
+    assert_eq!(jrd.profile_page(), Some("https://mastodon.social/@Gargron"));
+    assert_eq!(jrd.actor(), Some("https://mastodon.social/users/Gargron"));
+}
+
+#[test]
+fn jrd_without_links() {
+    let jrd: Jrd = serde_json::from_str(r#"{"subject": "https://mastodon.social/users/Gargron"}"#).unwrap();
+    assert!(jrd.aliases.is_empty());
+    assert_eq!(jrd.acct(), None);
+    assert_eq!(jrd.actor(), None);
+}